@@ -0,0 +1,66 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Generate one test per sample file and expected part, so `cargo test` reports each sample separately.
+// The tests call samples::assert_sample, which does the actual parsing and checking.
+fn main() {
+    println!("cargo:rerun-if-changed=samples");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let samples_dir = Path::new(&manifest_dir).join("samples");
+    let out_file = Path::new(&env::var("OUT_DIR").unwrap()).join("sample_tests.rs");
+
+    let mut paths = vec![];
+    if let Ok(years) = fs::read_dir(&samples_dir) {
+        for year in years.filter_map(|entry| entry.ok()) {
+            if let Ok(files) = fs::read_dir(year.path()) {
+                paths.extend(files.filter_map(|entry| entry.ok().map(|entry| entry.path())));
+            }
+        }
+    }
+    paths.sort();
+
+    let mut tests = String::new();
+    for path in paths {
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        let year = path
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_string_lossy();
+        let stem = path.file_stem().unwrap().to_string_lossy();
+        let name: String = format!("sample_{}_{}", year, stem)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        // Expected answers are `partN = ...` lines above the --- separator
+        let header = contents.split("\n---\n").next().unwrap_or("");
+        if header.len() == contents.len() {
+            continue;
+        }
+        for line in header.lines() {
+            let part = line
+                .split('=')
+                .next()
+                .and_then(|key| key.trim().strip_prefix("part"))
+                .and_then(|part| part.parse::<u32>().ok());
+            if let Some(part) = part {
+                tests.push_str(&format!(
+                    "#[test]\nfn {}_part{}() {{\n    super::assert_sample({:?}, {});\n}}\n\n",
+                    name,
+                    part,
+                    path.display().to_string(),
+                    part
+                ));
+            }
+        }
+    }
+
+    fs::write(out_file, tests).unwrap();
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::solution::Answer;
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+// Known correct answers, read from a TOML file keyed by year, day and part:
+//
+//     [2022.day1]
+//     part1 = 24000
+//     part2 = 45000
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u32, u32, u32), Answer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+impl Answers {
+    // A missing file is treated as an empty database, so check still reports every part as missing
+    pub fn load(path: &Path) -> Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Answers::parse(&contents)
+                .with_context(|| format!("could not parse {}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err).with_context(|| format!("could not read {}", path.display())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Answers> {
+        let table: toml::Table = contents.parse()?;
+        let mut expected = HashMap::new();
+
+        for (year, days) in table.iter() {
+            let year = parse_key(year, "")?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| anyhow!("[{}] should be a table of days", year))?;
+
+            for (day, parts) in days.iter() {
+                let day_number = parse_key(day, "day")?;
+                let parts = parts
+                    .as_table()
+                    .ok_or_else(|| anyhow!("[{}.{}] should be a table of parts", year, day))?;
+
+                for (part, answer) in parts.iter() {
+                    let part_number = parse_key(part, "part")?;
+                    let answer = match answer {
+                        toml::Value::Integer(n) => Answer::Number(*n),
+                        toml::Value::String(s) => Answer::Text(s.clone()),
+                        _ => {
+                            return Err(anyhow!(
+                                "{}.{}.{} should be a number or a string",
+                                year,
+                                day,
+                                part
+                            ))
+                        }
+                    };
+                    expected.insert((year, day_number, part_number), answer);
+                }
+            }
+        }
+
+        Ok(Answers { expected })
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&Answer> {
+        self.expected.get(&(year, day, part))
+    }
+
+    // Compare an answer against the database. Answers are compared as displayed,
+    // so a numeric answer recorded as a string still passes.
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &Answer) -> Status {
+        match self.get(year, day, part) {
+            Some(expected) if expected.to_string() == answer.to_string() => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+            },
+            None => Status::Missing,
+        }
+    }
+}
+
+// Parse keys like "2022", "day12" or "part1"
+fn parse_key(key: &str, prefix: &str) -> Result<u32> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse::<u32>().ok())
+        .ok_or_else(|| anyhow!("invalid key {}, expected {}N", key, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            r#"
+            [2022.day1]
+            part1 = 24000
+            part2 = 45000
+
+            [2022.day5]
+            part1 = "CMZ"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(2022, 1, 1), Some(&Answer::Number(24000)));
+        assert_eq!(answers.get(2022, 1, 2), Some(&Answer::Number(45000)));
+        assert_eq!(
+            answers.get(2022, 5, 1),
+            Some(&Answer::Text("CMZ".to_string()))
+        );
+        assert_eq!(answers.get(2022, 5, 2), None);
+        assert_eq!(answers.get(2021, 1, 1), None);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let cases = vec![
+            ("day outside a year", "[day1]\npart1 = 1"),
+            ("bad day key", "[2022.one]\npart1 = 1"),
+            ("bad part key", "[2022.day1]\nfirst = 1"),
+            ("bad answer type", "[2022.day1]\npart1 = 1.5"),
+        ];
+        for (description, input) in cases {
+            assert!(
+                Answers::parse(input).is_err(),
+                "{} did not get expected error",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[2022.day1]\npart1 = 24000\npart2 = \"45000\"").unwrap();
+
+        struct TestCase {
+            description: &'static str,
+            part: u32,
+            answer: Answer,
+            expected: Status,
+        }
+        let cases = vec![
+            TestCase {
+                description: "pass",
+                part: 1,
+                answer: Answer::Number(24000),
+                expected: Status::Pass,
+            },
+            TestCase {
+                description: "number recorded as a string",
+                part: 2,
+                answer: Answer::Number(45000),
+                expected: Status::Pass,
+            },
+            TestCase {
+                description: "fail",
+                part: 1,
+                answer: Answer::Number(1),
+                expected: Status::Fail {
+                    expected: Answer::Number(24000),
+                },
+            },
+            TestCase {
+                description: "missing",
+                part: 3,
+                answer: Answer::Number(1),
+                expected: Status::Missing,
+            },
+        ];
+        for case in cases {
+            let got = answers.check(2022, 1, case.part, &case.answer);
+            assert_eq!(got, case.expected, "{}", case.description);
+        }
+    }
+}
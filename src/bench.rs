@@ -0,0 +1,386 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+
+use crate::mem::Profile;
+use crate::runner::Solver;
+
+pub const README: &str = "Readme.md";
+
+// The generated table replaces everything between these markers in the Readme
+pub const TABLE_START: &str = "<!-- bench:start -->";
+pub const TABLE_END: &str = "<!-- bench:end -->";
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    // Time spent running the code before measuring, which also sizes the samples
+    pub warmup: Duration,
+    pub samples: usize,
+    // Roughly how long each sample runs for. Fast code is repeated within a sample
+    // so the timer's resolution doesn't dominate.
+    pub sample_time: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            warmup: Duration::from_millis(200),
+            samples: 30,
+            sample_time: Duration::from_millis(20),
+        }
+    }
+}
+
+// The median time per iteration, and the spread as the median absolute deviation from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub median: Duration,
+    pub spread: Duration,
+    pub samples: usize,
+}
+
+impl Measurement {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Measurement {
+        let count = samples.len();
+        let middle = median(&mut samples);
+        let mut deviations: Vec<Duration> = samples
+            .iter()
+            .map(|sample| sample.abs_diff(middle))
+            .collect();
+        Measurement {
+            median: middle,
+            spread: median(&mut deviations),
+            samples: count,
+        }
+    }
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+    samples.sort();
+    let middle = samples.len() / 2;
+    match samples.len() % 2 {
+        0 => (samples[middle - 1] + samples[middle]) / 2,
+        _ => samples[middle],
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let spread = match self.median.is_zero() {
+            true => 0.0,
+            false => self.spread.as_secs_f64() / self.median.as_secs_f64() * 100.0,
+        };
+        write!(f, "{} ± {:.1}%", format_duration(self.median), spread)
+    }
+}
+
+// Four significant figures in the largest unit that keeps the number above 1
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_secs_f64() * 1e9;
+    let (value, unit) = match nanos {
+        n if n < 1e3 => (n, "ns"),
+        n if n < 1e6 => (n / 1e3, "µs"),
+        n if n < 1e9 => (n / 1e6, "ms"),
+        n => (n / 1e9, "s"),
+    };
+    let decimals = match value {
+        v if v < 10.0 => 3,
+        v if v < 100.0 => 2,
+        v if v < 1000.0 => 1,
+        _ => 0,
+    };
+    format!("{:.*} {}", decimals, value, unit)
+}
+
+// Warm up, then time batches of iterations and return the time per iteration
+pub fn measure(settings: &Settings, mut f: impl FnMut() -> Result<()>) -> Result<Measurement> {
+    let mut iterations: u32 = 0;
+    let start = Instant::now();
+    while iterations == 0 || start.elapsed() < settings.warmup {
+        f()?;
+        iterations += 1;
+    }
+    let per_iteration = start.elapsed() / iterations;
+    let batch = match per_iteration.is_zero() {
+        true => 1,
+        false => (settings.sample_time.as_nanos() / per_iteration.as_nanos())
+            .clamp(1, u32::MAX as u128) as u32,
+    };
+
+    let mut samples = Vec::with_capacity(settings.samples);
+    for _ in 0..settings.samples.max(1) {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f()?;
+        }
+        samples.push(start.elapsed() / batch);
+    }
+    Ok(Measurement::from_samples(samples))
+}
+
+pub fn bench_generator(solver: &Solver, input: &str, settings: &Settings) -> Result<Measurement> {
+    measure(settings, || solver.generate(input).map(|_| ()))
+}
+
+pub fn bench_solution(solver: &Solver, input: &str, settings: &Settings) -> Result<Measurement> {
+    let generated = solver.generate(input)?;
+    measure(settings, || solver.solve(generated.as_ref()).map(|_| ()))
+}
+
+// One row of the benchmark table, for one part or variant
+#[derive(Debug)]
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    pub generator: Result<Measurement, String>,
+    pub solution: Result<Measurement, String>,
+    // Only filled in when memory is profiled
+    pub memory: Option<Result<Profile, String>>,
+}
+
+pub fn table(rows: &[Row]) -> String {
+    let cell = |measurement: &Result<Measurement, String>| match measurement {
+        Ok(measurement) => measurement.to_string(),
+        Err(_) => "N/A".to_string(),
+    };
+    // The memory columns only appear when some row was profiled
+    let memory = rows.iter().any(|row| row.memory.is_some());
+    let mut header = vec![
+        "Year".to_string(),
+        "Day".to_string(),
+        "Part".to_string(),
+        "Variant".to_string(),
+        "Generator".to_string(),
+        "Solution".to_string(),
+    ];
+    if memory {
+        header.push("Generator memory".to_string());
+        header.push("Solution memory".to_string());
+    }
+    let mut cells = vec![header];
+    cells.extend(rows.iter().map(|row| {
+        let mut cells = vec![
+            row.year.to_string(),
+            row.day.to_string(),
+            row.part.to_string(),
+            row.variant.unwrap_or("").to_string(),
+            cell(&row.generator),
+            cell(&row.solution),
+        ];
+        if memory {
+            let profile = row.memory.as_ref().and_then(|memory| memory.as_ref().ok());
+            for usage in [
+                profile.map(|profile| profile.generator),
+                profile.map(|profile| profile.solution),
+            ] {
+                cells.push(usage.map_or("N/A".to_string(), |usage| usage.to_string()));
+            }
+        }
+        cells
+    }));
+    markdown(&cells)
+}
+
+// Lay out rows of cells as a markdown table, padded so it also reads well as plain text.
+// The first row is the header.
+pub fn markdown(cells: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = (0..cells[0].len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |row: &[String]| {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let mut table = vec![line(&cells[0])];
+    table.push(line(
+        &widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<String>>(),
+    ));
+    table.extend(cells[1..].iter().map(|row| line(row)));
+    table.join("\n") + "\n"
+}
+
+// Replace the lines between the table markers, leaving everything else untouched
+pub fn update_readme(readme: &str, table: &str) -> Result<String> {
+    let start = readme
+        .find(TABLE_START)
+        .ok_or_else(|| anyhow!("could not find {} in the readme", TABLE_START))?
+        + TABLE_START.len();
+    let end = readme[start..].find(TABLE_END).ok_or_else(|| {
+        anyhow!(
+            "could not find {} after {} in the readme",
+            TABLE_END,
+            TABLE_START
+        )
+    })? + start;
+    Ok(format!("{}\n{}{}", &readme[..start], table, &readme[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mem::Usage;
+
+    fn micros(n: u64) -> Duration {
+        Duration::from_micros(n)
+    }
+
+    #[test]
+    fn test_from_samples() {
+        struct TestCase {
+            description: &'static str,
+            samples: Vec<Duration>,
+            expected: Measurement,
+        }
+        let cases = vec![
+            TestCase {
+                description: "odd number of samples",
+                samples: vec![micros(12), micros(10), micros(11)],
+                expected: Measurement {
+                    median: micros(11),
+                    spread: micros(1),
+                    samples: 3,
+                },
+            },
+            TestCase {
+                description: "outliers don't move the median or spread",
+                samples: vec![micros(10), micros(10), micros(11), micros(500), micros(10)],
+                expected: Measurement {
+                    median: micros(10),
+                    spread: micros(0),
+                    samples: 5,
+                },
+            },
+            TestCase {
+                description: "even number of samples",
+                samples: vec![micros(10), micros(20), micros(30), micros(40)],
+                expected: Measurement {
+                    median: micros(25),
+                    spread: micros(10),
+                    samples: 4,
+                },
+            },
+        ];
+        for case in cases {
+            let got = Measurement::from_samples(case.samples);
+            assert_eq!(got, case.expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let cases = vec![
+            (Duration::from_nanos(2), "2.000 ns"),
+            (Duration::from_nanos(65_570), "65.57 µs"),
+            (Duration::from_nanos(311_412), "311.4 µs"),
+            (Duration::from_micros(294_280), "294.3 ms"),
+            (Duration::from_secs(3), "3.000 s"),
+        ];
+        for (duration, expected) in cases {
+            assert_eq!(format_duration(duration), expected);
+        }
+
+        let measurement = Measurement {
+            median: micros(200),
+            spread: micros(3),
+            samples: 30,
+        };
+        assert_eq!(measurement.to_string(), "200.0 µs ± 1.5%");
+    }
+
+    #[test]
+    fn test_table() {
+        let measurement = Measurement {
+            median: micros(10),
+            spread: micros(1),
+            samples: 30,
+        };
+        let rows = vec![
+            Row {
+                year: 2022,
+                day: 12,
+                part: 1,
+                variant: None,
+                generator: Ok(measurement),
+                solution: Ok(measurement),
+                memory: None,
+            },
+            Row {
+                year: 2022,
+                day: 12,
+                part: 1,
+                variant: Some("slow"),
+                generator: Ok(measurement),
+                solution: Err("no input".to_string()),
+                memory: None,
+            },
+        ];
+        assert_eq!(
+            table(&rows),
+            "\
+| Year | Day | Part | Variant | Generator        | Solution         |
+| ---- | --- | ---- | ------- | ---------------- | ---------------- |
+| 2022 | 12  | 1    |         | 10.00 µs ± 10.0% | 10.00 µs ± 10.0% |
+| 2022 | 12  | 1    | slow    | 10.00 µs ± 10.0% | N/A              |
+"
+        );
+
+        let usage = Usage {
+            allocations: 3,
+            bytes: 2048,
+            peak: 1024,
+        };
+        let mut rows = rows;
+        rows[0].memory = Some(Ok(Profile {
+            generator: usage,
+            solution: Usage::default(),
+        }));
+        rows[1].memory = Some(Err("no input".to_string()));
+        assert_eq!(
+            table(&rows),
+            "\
+| Year | Day | Part | Variant | Generator        | Solution         | Generator memory                   | Solution memory          |
+| ---- | --- | ---- | ------- | ---------------- | ---------------- | ---------------------------------- | ------------------------ |
+| 2022 | 12  | 1    |         | 10.00 µs ± 10.0% | 10.00 µs ± 10.0% | 3 allocs, 2.00 KiB (peak 1.00 KiB) | 0 allocs, 0 B (peak 0 B) |
+| 2022 | 12  | 1    | slow    | 10.00 µs ± 10.0% | N/A              | N/A                                | N/A                      |
+"
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let readme = format!(
+            "# Title\n\n{}\nold table\n{}\n\nMore text\n",
+            TABLE_START, TABLE_END
+        );
+        assert_eq!(
+            update_readme(&readme, "new table\n").unwrap(),
+            format!(
+                "# Title\n\n{}\nnew table\n{}\n\nMore text\n",
+                TABLE_START, TABLE_END
+            )
+        );
+
+        assert!(update_readme("# Title\n", "new table\n").is_err());
+        assert!(update_readme(&format!("{}\nold table\n", TABLE_START), "new table\n").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::gen::{Generator, Rng};
+use crate::runner::Solver;
+use crate::solution::Answer;
+
+// Generated inputs are tried from the smallest size up, so the first disagreement
+// found is already small before it is shrunk
+pub const MAX_SIZE: usize = 12;
+pub const CASES_PER_SIZE: u64 = 10;
+
+// What one implementation made of an input, an error or panic counts as an outcome
+pub type Outcome = Result<Answer, String>;
+
+#[derive(Debug)]
+pub struct Disagreement {
+    // Where the input came from, e.g. "real input" or "generated input (seed 3, size 4)"
+    pub source: String,
+    // The smallest input found that still triggers the disagreement
+    pub input: String,
+    pub outcomes: Vec<(String, Outcome)>,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub implementations: Vec<String>,
+    // Inputs every implementation agreed on
+    pub agreed: usize,
+    // Inputs none of the implementations could solve, so there was nothing to compare
+    pub unsolved: usize,
+    pub disagreement: Option<Disagreement>,
+}
+
+// Run every implementation of one day and part on the real input (if there is one),
+// then on generated inputs, stopping at the first disagreement
+pub fn crosscheck(
+    solvers: &[&Solver],
+    real: Option<&str>,
+    generator: Option<Generator>,
+    seed: u64,
+) -> Report {
+    let mut report = Report {
+        implementations: solvers.iter().map(|solver| solver.name()).collect(),
+        agreed: 0,
+        unsolved: 0,
+        disagreement: None,
+    };
+
+    let generated = generator.into_iter().flat_map(|generator| {
+        (1..=MAX_SIZE).flat_map(move |size| {
+            (0..CASES_PER_SIZE).map(move |case| {
+                let seed = seed.wrapping_add(case);
+                let input = generator(&mut Rng::new(seed), size);
+                (
+                    format!("generated input (seed {}, size {})", seed, size),
+                    input,
+                )
+            })
+        })
+    });
+    let inputs = real
+        .map(|input| ("real input".to_string(), input.to_string()))
+        .into_iter()
+        .chain(generated);
+
+    for (source, input) in inputs {
+        let outcomes = outcomes(solvers, &input);
+        if outcomes.iter().all(Result::is_err) {
+            report.unsolved += 1;
+        } else if agree(&outcomes) {
+            report.agreed += 1;
+        } else {
+            let input = shrink(solvers, input, &outcomes);
+            let outcomes = self::outcomes(solvers, &input);
+            report.disagreement = Some(Disagreement {
+                source,
+                input,
+                outcomes: solvers
+                    .iter()
+                    .map(|solver| solver.name())
+                    .zip(outcomes)
+                    .collect(),
+            });
+            break;
+        }
+    }
+
+    report
+}
+
+// Parse the input once and solve it with every implementation. Every solver passed in
+// belongs to the same day, so any of them can parse for the others.
+pub fn outcomes(solvers: &[&Solver], input: &str) -> Vec<Outcome> {
+    let generated = match catch(|| solvers[0].generate(input)) {
+        Ok(generated) => generated,
+        Err(err) => return solvers.iter().map(|_| Err(err.clone())).collect(),
+    };
+    solvers
+        .iter()
+        .map(|solver| catch(|| solver.solve(generated.as_ref())))
+        .collect()
+}
+
+fn catch<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => Err(format!("{:#}", err)),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+// All the outcomes agree when every implementation got the same answer
+fn agree(outcomes: &[Outcome]) -> bool {
+    outcomes
+        .iter()
+        .all(|outcome| outcome.is_ok() && outcome == &outcomes[0])
+}
+
+// Greedily remove blocks separated by blank lines, then lines, then columns of rectangular
+// inputs, for as long as the implementations still disagree in the same way. The same way
+// means the same implementations fail, so an input that shrinks into garbage nobody can
+// parse is not kept.
+pub fn shrink(solvers: &[&Solver], input: String, outcomes: &[Outcome]) -> String {
+    let failing: Vec<bool> = outcomes.iter().map(Result::is_err).collect();
+    let still_disagrees = |candidate: &str| {
+        let outcomes = self::outcomes(solvers, candidate);
+        !agree(&outcomes)
+            && outcomes
+                .iter()
+                .map(Result::is_err)
+                .eq(failing.iter().copied())
+    };
+
+    let mut input = input;
+    'shrinking: loop {
+        for candidate in candidates(&input) {
+            if still_disagrees(&candidate) {
+                input = candidate;
+                continue 'shrinking;
+            }
+        }
+        return input;
+    }
+}
+
+// Every input one block, line or column smaller than `input`. Blocks go first, so
+// inputs made of groups of lines lose whole groups before they lose single lines.
+pub fn candidates(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut candidates = vec![];
+
+    let blocks: Vec<&str> = input.split("\n\n").collect();
+    if blocks.len() > 1 {
+        for skip in 0..blocks.len() {
+            let mut blocks = blocks.clone();
+            blocks.remove(skip);
+            candidates.push(blocks.join("\n\n"));
+        }
+    }
+
+    if lines.len() > 1 {
+        for skip in 0..lines.len() {
+            let mut lines = lines.clone();
+            lines.remove(skip);
+            candidates.push(lines.join("\n"));
+        }
+    }
+
+    let width = lines.first().map_or(0, |line| line.chars().count());
+    if width > 1 && lines.iter().all(|line| line.chars().count() == width) {
+        for skip in 0..width {
+            let candidate: Vec<String> = lines
+                .iter()
+                .map(|line| {
+                    line.chars()
+                        .enumerate()
+                        .filter(|(column, _)| *column != skip)
+                        .map(|(_, c)| c)
+                        .collect()
+                })
+                .collect();
+            candidates.push(candidate.join("\n"));
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Digits;
+    use crate::runner::{self, Year};
+
+    static REGISTRY: &[Year] = &[Year::new(2022, &[&Digits])];
+
+    fn digits(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| char::from(b'0' + rng.below(10) as u8))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_crosscheck() {
+        let solvers = runner::solvers(REGISTRY);
+        let solvers: Vec<&Solver> = solvers.iter().collect();
+
+        let report = crosscheck(&solvers, Some("123\n456"), None, 0);
+        assert_eq!(report.agreed, 1);
+        assert!(report.disagreement.is_none());
+
+        let report = crosscheck(&solvers, Some("x"), None, 0);
+        assert_eq!(report.unsolved, 1);
+        assert!(report.disagreement.is_none());
+
+        let report = crosscheck(&solvers, Some("1234\n5678\n9012\n3456\n7890"), None, 0);
+        let disagreement = report.disagreement.unwrap();
+        assert_eq!(disagreement.source, "real input");
+        assert_eq!(disagreement.input, "9\n7");
+        assert_eq!(
+            disagreement.outcomes,
+            vec![
+                ("2022 Day 1 - Part 1".to_string(), Ok(Answer::Number(16))),
+                (
+                    "2022 Day 1 - Part 1 - buggy".to_string(),
+                    Ok(Answer::Number(9))
+                ),
+            ]
+        );
+
+        let report = crosscheck(&solvers, None, Some(digits), 0);
+        let disagreement = report.disagreement.unwrap();
+        assert!(disagreement.source.starts_with("generated input"));
+        assert!(report.agreed > 0);
+        assert!(
+            disagreement.input == "97" || disagreement.input == "9\n7",
+            "{:?} is not the smallest disagreement",
+            disagreement.input
+        );
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        let solvers = crate::solvers();
+        for solver in solvers.iter().filter(|solver| solver.variant.is_some()) {
+            let implementations: Vec<&Solver> = solvers
+                .iter()
+                .filter(|other| {
+                    (other.year, other.day, other.part) == (solver.year, solver.day, solver.part)
+                })
+                .collect();
+            let generator = crate::gen::generator(solver.year, solver.day);
+            let report = crosscheck(&implementations, None, generator, 0);
+            assert!(report.disagreement.is_none(), "{:#?}", report.disagreement);
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+// Environment variables, which take precedence over the config file
+pub const TOKEN_VAR: &str = "AOC_SESSION_TOKEN";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// Where fetching is configured, under $XDG_CONFIG_HOME or ~/.config:
+//
+//     session = "<session cookie value>"
+//     base_url = "https://adventofcode.com"
+//     contact = "you@example.com"
+//
+// Every key is optional.
+pub const CONFIG_FILE: &str = "advent-of-code/config.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    // Who to get in touch with about the requests, sent in the User-Agent
+    pub contact: Option<String>,
+}
+
+impl Config {
+    pub fn load() -> Result<Config> {
+        let path = config_path();
+        let file = match &path {
+            Some(path) if path.exists() => Some(
+                fs::read_to_string(path)
+                    .with_context(|| format!("could not read {}", path.display()))?,
+            ),
+            _ => None,
+        };
+        let var = |name| {
+            env::var(name)
+                .ok()
+                .filter(|value: &String| !value.is_empty())
+        };
+        Config::from_sources(
+            var(TOKEN_VAR),
+            var(BASE_URL_VAR),
+            var(CONTACT_VAR),
+            file.as_deref(),
+        )
+        .with_context(|| match path {
+            Some(path) => format!("could not parse {}", path.display()),
+            None => "could not parse the config file".to_string(),
+        })
+    }
+
+    // Each setting comes from its environment variable if set, then the config file
+    fn from_sources(
+        session: Option<String>,
+        base_url: Option<String>,
+        contact: Option<String>,
+        file: Option<&str>,
+    ) -> Result<Config> {
+        let table: toml::Table = file.unwrap_or("").parse()?;
+        let setting = |key: &str| -> Result<Option<String>> {
+            match table.get(key) {
+                Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+                Some(_) => Err(anyhow!("{} should be a string", key)),
+                None => Ok(None),
+            }
+        };
+        Ok(Config {
+            session: session.or(setting("session")?),
+            base_url: base_url
+                .or(setting("base_url")?)
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            contact: contact.or(setting("contact")?),
+        })
+    }
+
+    pub(crate) fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            anyhow!(
+                "no session token, set {} or session in ~/.config/{}",
+                TOKEN_VAR,
+                CONFIG_FILE
+            )
+        })
+    }
+
+    // The URL of a page of a day's puzzle, like input or answer
+    pub(crate) fn url(&self, year: u32, day: u32, page: &str) -> String {
+        format!(
+            "{}/{}/day/{}/{}",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day,
+            page
+        )
+    }
+
+    // Advent of Code asks automated tools to say what they are and who runs them
+    pub fn user_agent(&self) -> String {
+        let contact = match &self.contact {
+            Some(contact) => format!("; contact {}", contact),
+            None => String::new(),
+        };
+        format!(
+            "{}/{} (caches inputs, throttles answers{})",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            contact
+        )
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join(CONFIG_FILE))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    // The input was already on disk, so nothing was requested
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Make sure a day's input is at `path`, downloading it only if the file doesn't exist yet.
+// Inputs never change, so an existing file is always trusted.
+pub fn fetch(config: &Config, year: u32, day: u32, path: &Path) -> Result<Fetched> {
+    if path.exists() {
+        return Ok(Fetched::Cached(path.to_path_buf()));
+    }
+    let input = download(config, year, day)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create {}", parent.display()))?;
+    }
+    // Write to a temporary file first, so an interrupted fetch never leaves a partial
+    // input behind that would be trusted from then on
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input).with_context(|| format!("could not write {}", partial.display()))?;
+    fs::rename(&partial, path).with_context(|| format!("could not write {}", path.display()))?;
+    Ok(Fetched::Downloaded(path.to_path_buf()))
+}
+
+// An agent with a timeout, so a server that stops answering can't hang the runner
+pub(crate) fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .into()
+}
+
+// The server answers requests with a missing or expired session with these
+pub(crate) fn is_logged_out(status: u16) -> bool {
+    matches!(status, 400 | 401 | 500)
+}
+
+pub fn download(config: &Config, year: u32, day: u32) -> Result<String> {
+    let session = config.session()?;
+    let url = config.url(year, day, "input");
+    let response = agent()
+        .get(&url)
+        .header("Cookie", &format!("session={}", session))
+        .header("User-Agent", &config.user_agent())
+        .call();
+    match response {
+        Ok(mut response) => response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("could not read the response from {}", url)),
+        Err(ureq::Error::StatusCode(404)) => Err(anyhow!(
+            "day {} of {} is not available yet ({} returned 404)",
+            day,
+            year,
+            url
+        )),
+        Err(ureq::Error::StatusCode(status)) if is_logged_out(status) => Err(anyhow!(
+            "{} returned {}, the session token has probably expired",
+            url,
+            status
+        )),
+        Err(err) => Err(err).with_context(|| format!("could not download {}", url)),
+    }
+}
+
+// A stand-in for the Advent of Code server, for testing requests without the network
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    // What the server was sent: the request line, the headers and the body
+    pub struct Request {
+        pub lines: Vec<String>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.lines.iter().skip(1).find_map(|line| {
+                let (key, value) = line.split_once(": ")?;
+                key.eq_ignore_ascii_case(name).then_some(value)
+            })
+        }
+    }
+
+    // Serve `responses` in order on a local port, one per connection, and send back
+    // each request. Returns the base URL to make requests to.
+    pub fn server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let lines: Vec<String> = reader
+                    .by_ref()
+                    .lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                let mut request = Request {
+                    lines,
+                    body: String::new(),
+                };
+                let length = request
+                    .header("Content-Length")
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.body = String::from_utf8(content).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                sender.send(request).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_url: String) -> Config {
+        Config {
+            session: Some("abc123".to_string()),
+            base_url,
+            contact: Some("someone@example.com".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_from_sources() {
+        struct TestCase {
+            description: &'static str,
+            session: Option<&'static str>,
+            file: Option<&'static str>,
+            expected: Option<(Option<&'static str>, &'static str)>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "nothing set",
+                session: None,
+                file: None,
+                expected: Some((None, DEFAULT_BASE_URL)),
+            },
+            TestCase {
+                description: "config file",
+                session: None,
+                file: Some("session = \"file\"\nbase_url = \"http://localhost:1\""),
+                expected: Some((Some("file"), "http://localhost:1")),
+            },
+            TestCase {
+                description: "environment wins",
+                session: Some("env"),
+                file: Some("session = \"file\""),
+                expected: Some((Some("env"), DEFAULT_BASE_URL)),
+            },
+            TestCase {
+                description: "not a string",
+                session: None,
+                file: Some("session = 12"),
+                expected: None,
+            },
+        ];
+        for case in cases {
+            let got = Config::from_sources(case.session.map(String::from), None, None, case.file);
+            assert_eq!(
+                got.ok()
+                    .map(|config| (config.session, config.base_url))
+                    .as_ref()
+                    .map(|(session, url)| (session.as_deref(), url.as_str())),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, requests) = stub::server(vec![(200, "1000\n2000\n")]);
+        let path = env::temp_dir()
+            .join(format!("aoc-fetch-test-{}", std::process::id()))
+            .join("2022/day1.txt");
+        let _ = fs::remove_file(&path);
+
+        let config = config(url);
+        assert_eq!(
+            fetch(&config, 2022, 1, &path).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.lines[0], "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        let user_agent = request.header("User-Agent").unwrap();
+        assert!(user_agent.starts_with("advent-of-code/"), "{}", user_agent);
+        assert!(user_agent.contains("someone@example.com"), "{}", user_agent);
+
+        // The stub only answers once, so a second request would fail
+        assert_eq!(
+            fetch(&config, 2022, 1, &path).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        fs::remove_dir_all(path.parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_download_errors() {
+        let (url, _requests) = stub::server(vec![(404, "Not Found"), (400, "Please log in")]);
+        let config = config(url);
+        let err = download(&config, 2022, 25).unwrap_err().to_string();
+        assert!(err.contains("not available yet"), "{}", err);
+        let err = download(&config, 2022, 1).unwrap_err().to_string();
+        assert!(err.contains("session token"), "{}", err);
+
+        let config = Config {
+            session: None,
+            ..config
+        };
+        let err = download(&config, 2022, 1).unwrap_err().to_string();
+        assert!(err.contains(TOKEN_VAR), "{}", err);
+    }
+}
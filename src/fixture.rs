@@ -0,0 +1,58 @@
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution, SolveError, Variant};
+
+// A toy day for testing the registry, runner and crosscheck. It sums the digits in the
+// input, and no input containing an x can be parsed. The buggy variant forgets any 7
+// that comes after a 9, and part 2 hasn't been solved.
+pub struct Digits;
+
+fn sum(digits: &[usize], buggy: bool) -> Result<Answer, SolveError> {
+    if digits.is_empty() {
+        return Err(SolveError::EmptyInput);
+    }
+    let mut seen_nine = false;
+    let mut sum = 0;
+    for &digit in digits {
+        seen_nine |= digit == 9;
+        if !(buggy && seen_nine && digit == 7) {
+            sum += digit;
+        }
+    }
+    Ok(sum.into())
+}
+
+impl Solution for Digits {
+    const DAY: u32 = 1;
+    const PARTS: &'static [u32] = &[1];
+    const VARIANTS: &'static [Variant<Vec<usize>>] = &[Variant {
+        part: 1,
+        name: "buggy",
+        solve: |digits| sum(digits, true),
+    }];
+
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        if let Some(index) = input.find('x') {
+            return Err(ParseError::at(
+                1,
+                input,
+                &input[index..index + 1],
+                "expected a digit",
+            ));
+        }
+        Ok(input
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|digit| digit as usize)
+            .collect())
+    }
+
+    fn part1(digits: &Vec<usize>) -> Result<Answer, SolveError> {
+        sum(digits, false)
+    }
+
+    fn part2(_digits: &Vec<usize>) -> Result<Answer, SolveError> {
+        Err(SolveError::Unsolved)
+    }
+}
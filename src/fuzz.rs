@@ -0,0 +1,100 @@
+use crate::solution::Registered;
+use crate::YEARS;
+
+// The registered solution of one day, whose parser a fuzz target feeds
+pub fn registered(year: u32, day: u32) -> Option<&'static dyn Registered> {
+    let year = YEARS.iter().find(|registered| registered.year == year)?;
+    year.solutions
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+}
+
+// The body of every fuzz target in fuzz/: parse `data` as the input of a day. A parser
+// may reject any input with an error, but must never panic. Inputs are read as text
+// before they reach a parser, so bytes that aren't UTF-8 are skipped.
+pub fn parse(year: u32, day: u32, data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let solution =
+        registered(year, day).expect("fuzz targets are only written for registered days");
+    let _ = solution.parse(input);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_every_day_has_a_fuzz_target() {
+        let targets = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/fuzz_targets");
+        for solver in crate::solvers() {
+            let target = targets.join(format!("year{}_day{}.rs", solver.year, solver.day));
+            assert!(target.exists(), "no fuzz target at {}", target.display());
+        }
+    }
+
+    // Inputs the fuzz targets found crashes on, and inputs like them. Each must be rejected.
+    #[test]
+    fn test_regressions() {
+        struct TestCase {
+            description: &'static str,
+            day: u32,
+            input: &'static str,
+        }
+        let cases = vec![
+            TestCase {
+                description: "calories adding up past usize",
+                day: 1,
+                input: "18446744073709551615\n1\n\n5",
+            },
+            TestCase {
+                description: "more stacks than the line of numbers has room for",
+                day: 5,
+                input: "[A]\n 1   99999999999\n\nmove 1 from 1 to 1",
+            },
+            TestCase {
+                description: "non-ASCII crates",
+                day: 5,
+                input: "[é]\n 1 \n\nmove 1 from 1 to 1",
+            },
+            TestCase {
+                description: "non-ASCII register",
+                day: 10,
+                input: "addé 1",
+            },
+            TestCase {
+                description: "short instruction",
+                day: 10,
+                input: "ad 1",
+            },
+            TestCase {
+                description: "operation without a magnitude",
+                day: 11,
+                input: "Monkey 0:\n  Starting items: 1\n  Operation: new = old *",
+            },
+            TestCase {
+                description: "unopened list",
+                day: 13,
+                input: "]\n[1]",
+            },
+            TestCase {
+                description: "number outside a list",
+                day: 13,
+                input: "[1]2\n[1]",
+            },
+        ];
+        for case in cases {
+            let solution = registered(2022, case.day).unwrap();
+            assert!(
+                solution.parse(case.input).is_err(),
+                "{}: parsed {:?}",
+                case.description,
+                case.input
+            );
+        }
+    }
+}
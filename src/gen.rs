@@ -0,0 +1,452 @@
+use std::ops::RangeInclusive;
+
+// A small seeded random number generator (splitmix64), so a generated input can be
+// reproduced from its seed without pulling in a dependency
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n, n must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    // Fisher-Yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// Generates a puzzle input for a day. Size roughly scales the input, so small sizes
+// give inputs that are easy to read when something goes wrong.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub fn generator(year: u32, day: u32) -> Option<Generator> {
+    match (year, day) {
+        (2022, 1) => Some(day1),
+        (2022, 2) => Some(day2),
+        (2022, 3) => Some(day3),
+        (2022, 4) => Some(day4),
+        (2022, 5) => Some(day5),
+        (2022, 6) => Some(day6),
+        (2022, 8) => Some(day8),
+        (2022, 10) => Some(day10),
+        (2022, 11) => Some(day11),
+        (2022, 12) => Some(day12),
+        (2022, 13) => Some(day13),
+        _ => None,
+    }
+}
+
+// Blocks of calories, one block per elf. Part 2 adds up the top three, so there are
+// always at least three elves.
+fn day1(rng: &mut Rng, size: usize) -> String {
+    let elves = rng.range(3..=size.max(3));
+    (0..elves)
+        .map(|_| {
+            let food = rng.range(1..=size.max(1));
+            (0..food)
+                .map(|_| rng.range(1000..=9999).to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+// Rounds of rock paper scissors, what the opponent plays and the second column
+fn day2(rng: &mut Rng, size: usize) -> String {
+    let rounds = rng.range(1..=size.max(1));
+    (0..rounds)
+        .map(|_| {
+            let opponent = char::from(b'A' + rng.below(3) as u8);
+            let response = char::from(b'X' + rng.below(3) as u8);
+            format!("{} {}", opponent, response)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Groups of three rucksacks. Each rucksack has exactly one item type in both
+// compartments, and each group exactly one badge that all three carry. Every rucksack
+// draws its other items from its own share of the alphabet, so nothing else is shared.
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let groups = rng.range(1..=size.max(1));
+    let mut rucksacks = vec![];
+    for _ in 0..groups {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        // 51 items left, 17 for each rucksack
+        for own in items.chunks(17) {
+            let misplaced = own[0];
+            let mut compartments = [vec![misplaced], vec![misplaced]];
+            // The badge goes in one compartment only, or it would be misplaced too
+            compartments[rng.below(2)].push(badge);
+            for &item in &own[1..rng.range(1..=size.min(16) + 1)] {
+                compartments[rng.below(2)].push(item);
+            }
+            // Both compartments hold the same number of items, repeats make up the difference
+            let length = compartments[0].len().max(compartments[1].len());
+            for compartment in compartments.iter_mut() {
+                while compartment.len() < length {
+                    let item = compartment[rng.below(compartment.len())];
+                    compartment.push(item);
+                }
+                rng.shuffle(compartment);
+            }
+            rucksacks.push(compartments.concat().into_iter().collect::<String>());
+        }
+    }
+    rucksacks.join("\n")
+}
+
+// Pairs of section assignments, some of them overlapping
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let pairs = rng.range(1..=size.max(1));
+    let last = size.max(1) * 2;
+    let mut assignment = || {
+        let start = rng.range(1..=last);
+        let end = rng.range(start..=last);
+        format!("{}-{}", start, end)
+    };
+    (0..pairs)
+        .map(|_| format!("{},{}", assignment(), assignment()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// A drawing of two to nine stacks of crates and moves between them. No move takes the
+// last crate off a stack, so every stack has a crate on top at the end. Some stack
+// starts with two crates, and every move leaves one behind, so there's always a move.
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let stacks = rng.range(2..=size.clamp(2, 9));
+    let mut heights: Vec<usize> = (0..stacks).map(|_| rng.range(1..=size.max(1))).collect();
+    let stack = rng.below(stacks);
+    heights[stack] = heights[stack].max(2);
+    let tallest = *heights.iter().max().unwrap();
+
+    let mut drawing: Vec<String> = (0..tallest)
+        .rev()
+        .map(|level| {
+            heights
+                .iter()
+                .map(|&height| match height > level {
+                    true => format!("[{}]", char::from(b'A' + rng.below(26) as u8)),
+                    false => "   ".to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+    drawing.push(
+        (1..=stacks)
+            .map(|id| format!(" {} ", id))
+            .collect::<Vec<String>>()
+            .join(" "),
+    );
+
+    let mut moves = vec![];
+    for _ in 0..rng.range(1..=size.max(1)) {
+        let sources: Vec<usize> = (0..stacks).filter(|&stack| heights[stack] > 1).collect();
+        let source = sources[rng.below(sources.len())];
+        let destination = (source + rng.range(1..=stacks - 1)) % stacks;
+        let quantity = rng.range(1..=heights[source] - 1);
+        heights[source] -= quantity;
+        heights[destination] += quantity;
+        moves.push(format!(
+            "move {} from {} to {}",
+            quantity,
+            source + 1,
+            destination + 1
+        ));
+    }
+
+    format!("{}\n\n{}", drawing.join("\n"), moves.join("\n"))
+}
+
+// A datastream of letters with a run of fourteen different letters somewhere in it, so
+// both markers are found. The noise around it uses few letters, so it repeats a lot.
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let noise = |rng: &mut Rng| -> String {
+        (0..rng.below(size * 4 + 1))
+            .map(|_| char::from(b'a' + rng.below(4) as u8))
+            .collect()
+    };
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    let marker: String = marker[..14].iter().collect();
+    format!("{}{}{}", noise(rng), marker, noise(rng))
+}
+
+// A grid of tree heights, up to size x size
+fn day8(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let rows = rng.range(1..=size);
+    let columns = rng.range(1..=size);
+    (0..rows)
+        .map(|_| {
+            (0..columns)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// A program of noops and additions to the x register
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let instructions = rng.range(1..=size.max(1) * 20);
+    (0..instructions)
+        .map(|_| match rng.chance(1, 3) {
+            true => "noop".to_string(),
+            false => format!("addx {}", rng.range(0..=40) as isize - 20),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Up to size monkeys, each testing for a different prime so the worry levels stay small
+// enough to square in part 2. Part 1 doesn't keep the worry levels down that way, so
+// monkeys that would overflow within its twenty rounds are drawn again, and after enough
+// tries the monkeys only add.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let count = rng.range(2..=size.clamp(2, PRIMES.len()));
+    let mut attempt = 0;
+    let monkeys = loop {
+        attempt += 1;
+        let mut primes = PRIMES;
+        rng.shuffle(&mut primes);
+        let monkeys: Vec<GenMonkey> = (0..count)
+            .map(|id| {
+                let operation = match attempt <= 20 && rng.chance(1, 2) {
+                    true if rng.chance(1, 4) => ('*', None),
+                    true => ('*', Some(rng.range(2..=19))),
+                    false if rng.chance(1, 8) => ('+', None),
+                    false => ('+', Some(rng.range(1..=9))),
+                };
+                let items = (0..rng.range(0..=4)).map(|_| rng.range(50..=99)).collect();
+                let mut target = || (id + rng.range(1..=count - 1)) % count;
+                GenMonkey {
+                    items,
+                    operation,
+                    divisor: primes[id],
+                    targets: (target(), target()),
+                }
+            })
+            .collect();
+        if survives_part1(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(id, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(usize::to_string).collect();
+            let magnitude = monkey
+                .operation
+                .1
+                .map_or("old".to_string(), |n| n.to_string());
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = old {} {}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}",
+                id,
+                items.join(", "),
+                monkey.operation.0,
+                magnitude,
+                monkey.divisor,
+                monkey.targets.0,
+                monkey.targets.1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+struct GenMonkey {
+    items: Vec<usize>,
+    // + or *, and by how much, None for old
+    operation: (char, Option<usize>),
+    divisor: usize,
+    // Where items go when the test passes and when it fails
+    targets: (usize, usize),
+}
+
+// Play part 1 with checked arithmetic
+fn survives_part1(monkeys: &[GenMonkey]) -> bool {
+    let mut items: Vec<Vec<usize>> = monkeys.iter().map(|monkey| monkey.items.clone()).collect();
+    for _ in 0..20 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[id]) {
+                let by = monkey.operation.1.unwrap_or(item);
+                let worry = match monkey.operation.0 {
+                    '*' => item.checked_mul(by),
+                    _ => item.checked_add(by),
+                };
+                let Some(worry) = worry else {
+                    return false;
+                };
+                let worry = worry / 3;
+                let target = match worry % monkey.divisor == 0 {
+                    true => monkey.targets.0,
+                    false => monkey.targets.1,
+                };
+                items[target].push(worry);
+            }
+        }
+    }
+    true
+}
+
+// A height map that always has a path from S to E. The path snakes back and forth
+// across every row, climbing at most one step at a time, and reaches z by the end.
+// Neighbouring rows of the snake give shortcuts, so the shortest path is not the snake.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    // S to E takes at least 25 climbs, so the snake needs at least 26 cells
+    let columns = rng.range(2..=size.max(2));
+    let min_rows = 26_usize.div_ceil(columns).max(2);
+    let rows = rng.range(min_rows..=size.max(min_rows));
+    let cells = rows * columns;
+
+    let mut heights = vec![0_u8; cells];
+    let mut height = 0;
+    for (step, cell) in heights.iter_mut().enumerate().skip(1) {
+        let remaining = cells - 1 - step;
+        if height < 25 && (25 - height >= remaining || rng.chance(1, 3)) {
+            height += 1;
+        }
+        *cell = height as u8;
+    }
+
+    let mut grid = vec![vec!['a'; columns]; rows];
+    for (step, height) in heights.iter().enumerate() {
+        let row = step / columns;
+        let column = match row % 2 {
+            0 => step % columns,
+            _ => columns - 1 - step % columns,
+        };
+        grid[row][column] = match step {
+            0 => 'S',
+            step if step == cells - 1 => 'E',
+            _ => char::from(b'a' + height),
+        };
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Pairs of packets, lists nested a few deep
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let pairs = rng.range(1..=size.max(1));
+    (0..pairs)
+        .map(|_| format!("{}\n{}", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let length = rng.below(5);
+    let values: Vec<String> = (0..length)
+        .map(|_| match depth < 3 && rng.chance(1, 3) {
+            true => packet(rng, depth + 1),
+            false => rng.range(0..=10).to_string(),
+        })
+        .collect();
+    format!("[{}]", values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::scaffold;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+        let a: Vec<u64> = (0..4).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..4).map(|_| b.next_u64()).collect();
+        let c: Vec<u64> = (0..4).map(|_| c.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let mut rng = Rng::new(0);
+        for _ in 0..100 {
+            assert!((3..=5).contains(&rng.range(3..=5)));
+        }
+    }
+
+    // A scaffolded day needs a generator once it has moved on from the template, which
+    // can't be written before its input format is known
+    #[test]
+    fn test_every_day_has_a_generator() {
+        for solver in crate::solvers() {
+            let module = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join(format!("src/year{}/day{}.rs", solver.year, solver.day));
+            let source = fs::read_to_string(&module).unwrap();
+            if scaffold::is_template(&source, solver.day) {
+                continue;
+            }
+            assert!(
+                generator(solver.year, solver.day).is_some(),
+                "no generator for {}",
+                solver.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_generators_parse() {
+        for solver in crate::solvers() {
+            let Some(generator) = generator(solver.year, solver.day) else {
+                continue;
+            };
+            for size in 1..=8 {
+                let input = generator(&mut Rng::new(size as u64), size);
+                if let Err(err) = solver.run(&input) {
+                    panic!(
+                        "{} failed on generated input {:?}: {:#}",
+                        solver.name(),
+                        input,
+                        err
+                    );
+                }
+            }
+        }
+    }
+}
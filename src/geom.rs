@@ -0,0 +1,620 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// The integer types points are made of
+pub trait Coordinate: Copy + Ord {
+    // How far apart two coordinates are, whatever their signs
+    fn distance(self, other: Self) -> usize;
+}
+
+impl Coordinate for i64 {
+    fn distance(self, other: i64) -> usize {
+        self.abs_diff(other) as usize
+    }
+}
+
+impl Coordinate for usize {
+    fn distance(self, other: usize) -> usize {
+        self.abs_diff(other)
+    }
+}
+
+// x counts right and y counts down, the way puzzle inputs are printed, so a point
+// in a grid has x as its column and y as its row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// Signed points can be anywhere, and double as offsets between points
+pub type IPoint2 = Point2<i64>;
+pub type IPoint3 = Point3<i64>;
+
+// Unsigned points can index into grids and slices
+pub type UPoint2 = Point2<usize>;
+pub type UPoint3 = Point3<usize>;
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    // The distance moving only along the axes
+    pub fn manhattan(self, other: Point2<T>) -> usize {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    // The distance when diagonal moves count as one step, like a king in chess
+    pub fn chebyshev(self, other: Point2<T>) -> usize {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn manhattan(self, other: Point3<T>) -> usize {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> usize {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+}
+
+impl IPoint2 {
+    pub fn checked_add(self, other: IPoint2) -> Option<IPoint2> {
+        Some(Point2::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    // Each coordinate reduced to -1, 0 or 1, turning the difference between two points
+    // into a single step from one towards the other
+    pub fn signum(self) -> IPoint2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    // None if either coordinate is negative
+    pub fn unsigned(self) -> Option<UPoint2> {
+        Some(Point2::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl UPoint2 {
+    pub fn signed(self) -> IPoint2 {
+        Point2::new(self.x as i64, self.y as i64)
+    }
+
+    // None if the offset would move the point below zero
+    pub fn checked_offset(self, offset: IPoint2) -> Option<UPoint2> {
+        Some(Point2::new(
+            self.x.checked_add_signed(isize::try_from(offset.x).ok()?)?,
+            self.y.checked_add_signed(isize::try_from(offset.y).ok()?)?,
+        ))
+    }
+
+    // Offset the point, wrapping around the edges of a `size.x` by `size.y` area
+    // like the screen in an arcade game. The point must be inside the area.
+    pub fn wrapping_offset(self, offset: IPoint2, size: UPoint2) -> UPoint2 {
+        let wrap = |coordinate: usize, offset: i64, size: usize| {
+            (coordinate as i64 + offset).rem_euclid(size as i64) as usize
+        };
+        Point2::new(
+            wrap(self.x, offset.x, size.x),
+            wrap(self.y, offset.y, size.y),
+        )
+    }
+}
+
+impl IPoint3 {
+    pub fn checked_add(self, other: IPoint3) -> Option<IPoint3> {
+        Some(Point3::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+            self.z.checked_add(other.z)?,
+        ))
+    }
+
+    pub fn unsigned(self) -> Option<UPoint3> {
+        Some(Point3::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+            usize::try_from(self.z).ok()?,
+        ))
+    }
+
+    // The six points sharing a face with this one
+    pub fn faces(self) -> [IPoint3; 6] {
+        [
+            self + Point3::new(1, 0, 0),
+            self + Point3::new(-1, 0, 0),
+            self + Point3::new(0, 1, 0),
+            self + Point3::new(0, -1, 0),
+            self + Point3::new(0, 0, 1),
+            self + Point3::new(0, 0, -1),
+        ]
+    }
+}
+
+impl UPoint3 {
+    pub fn signed(self) -> IPoint3 {
+        Point3::new(self.x as i64, self.y as i64, self.z as i64)
+    }
+
+    pub fn checked_offset(self, offset: IPoint3) -> Option<UPoint3> {
+        Some(Point3::new(
+            self.x.checked_add_signed(isize::try_from(offset.x).ok()?)?,
+            self.y.checked_add_signed(isize::try_from(offset.y).ok()?)?,
+            self.z.checked_add_signed(isize::try_from(offset.z).ok()?)?,
+        ))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Point2<T>;
+
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+// Scale a point, e.g. an offset by a number of steps
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn mul(self, scale: T) -> Point2<T> {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Point2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Point2<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, scale: T) -> Point3<T> {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Point3<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Point3<T>) {
+        *self = *self - other;
+    }
+}
+
+// The four ways to move on a grid, with Up towards smaller y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from Up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn offset(self) -> IPoint2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    // The direction a single step moves in, None for anything but one step up, down,
+    // left or right
+    pub fn from_offset(offset: IPoint2) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    // A quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    // A quarter turn anticlockwise
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    // How puzzle descriptions draw a step in this direction
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+// The eight ways to move when diagonals are allowed, with North towards smaller y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compass {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Compass {
+    // Clockwise from North
+    pub const ALL: [Compass; 8] = [
+        Compass::North,
+        Compass::NorthEast,
+        Compass::East,
+        Compass::SouthEast,
+        Compass::South,
+        Compass::SouthWest,
+        Compass::West,
+        Compass::NorthWest,
+    ];
+
+    pub fn offset(self) -> IPoint2 {
+        match self {
+            Compass::North => Point2::new(0, -1),
+            Compass::NorthEast => Point2::new(1, -1),
+            Compass::East => Point2::new(1, 0),
+            Compass::SouthEast => Point2::new(1, 1),
+            Compass::South => Point2::new(0, 1),
+            Compass::SouthWest => Point2::new(-1, 1),
+            Compass::West => Point2::new(-1, 0),
+            Compass::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    pub fn from_offset(offset: IPoint2) -> Option<Compass> {
+        Compass::ALL
+            .into_iter()
+            .find(|compass| compass.offset() == offset)
+    }
+
+    // An eighth of a turn clockwise
+    pub fn turn_right(self) -> Compass {
+        Compass::ALL[(self as usize + 1) % 8]
+    }
+
+    // An eighth of a turn anticlockwise
+    pub fn turn_left(self) -> Compass {
+        Compass::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Compass {
+        Compass::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Compass {
+        match direction {
+            Direction::Up => Compass::North,
+            Direction::Right => Compass::East,
+            Direction::Down => Compass::South,
+            Direction::Left => Compass::West,
+        }
+    }
+}
+
+// The smallest rectangle containing a set of points, including its edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    pub fn new(point: Point2<T>) -> BoundingBox<T> {
+        BoundingBox {
+            min: point,
+            max: point,
+        }
+    }
+
+    // None if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<BoundingBox<T>> {
+        let mut points = points.into_iter();
+        let mut bounds = BoundingBox::new(points.next()?);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    // Grow the box to contain `point`
+    pub fn include(&mut self, point: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    // The number of columns the box covers
+    pub fn width(&self) -> usize {
+        self.min.x.distance(self.max.x) + 1
+    }
+
+    // The number of rows the box covers
+    pub fn height(&self) -> usize {
+        self.min.y.distance(self.max.y) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        struct TestCase {
+            description: &'static str,
+            a: IPoint2,
+            b: IPoint2,
+            expected: (usize, usize),
+        }
+        let cases = vec![
+            TestCase {
+                description: "same point",
+                a: Point2::new(3, 4),
+                b: Point2::new(3, 4),
+                expected: (0, 0),
+            },
+            TestCase {
+                description: "across the origin",
+                a: Point2::new(-2, 3),
+                b: Point2::new(4, -1),
+                expected: (10, 6),
+            },
+            TestCase {
+                description: "diagonal",
+                a: Point2::new(0, 0),
+                b: Point2::new(5, 5),
+                expected: (10, 5),
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                (case.a.manhattan(case.b), case.a.chebyshev(case.b)),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+
+        let a: UPoint3 = Point3::new(1, 5, 2);
+        let b: UPoint3 = Point3::new(4, 1, 2);
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (7, 4));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: IPoint2 = Point2::new(2, -3);
+        let b: IPoint2 = Point2::new(-1, 5);
+        assert_eq!(a + b, Point2::new(1, 2));
+        assert_eq!(a - b, Point2::new(3, -8));
+        assert_eq!(-a, Point2::new(-2, 3));
+        assert_eq!(a * 3, Point2::new(6, -9));
+        assert_eq!((a - b).signum(), Point2::new(1, -1));
+        assert_eq!(
+            Point2::new(i64::MAX, 0).checked_add(Point2::new(1, 0)),
+            None
+        );
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        let d: IPoint3 = Point3::new(1, 2, 3);
+        assert_eq!(d + d * 2 - d, Point3::new(2, 4, 6));
+        assert_eq!(
+            d.faces()
+                .iter()
+                .filter(|face| face.manhattan(d) == 1)
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        struct TestCase {
+            description: &'static str,
+            point: UPoint2,
+            offset: IPoint2,
+            checked: Option<UPoint2>,
+            wrapping: UPoint2,
+        }
+        // Wrapping happens inside a 5 by 4 area
+        let size = Point2::new(5, 4);
+        let cases = vec![
+            TestCase {
+                description: "inside",
+                point: Point2::new(1, 1),
+                offset: Point2::new(2, 1),
+                checked: Some(Point2::new(3, 2)),
+                wrapping: Point2::new(3, 2),
+            },
+            TestCase {
+                description: "below zero",
+                point: Point2::new(0, 2),
+                offset: Point2::new(-1, 0),
+                checked: None,
+                wrapping: Point2::new(4, 2),
+            },
+            TestCase {
+                description: "past the far edge",
+                point: Point2::new(4, 3),
+                offset: Point2::new(1, 3),
+                checked: Some(Point2::new(5, 6)),
+                wrapping: Point2::new(0, 2),
+            },
+            TestCase {
+                description: "more than once around",
+                point: Point2::new(2, 0),
+                offset: Point2::new(-12, -9),
+                checked: None,
+                wrapping: Point2::new(0, 3),
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                case.point.checked_offset(case.offset),
+                case.checked,
+                "{}",
+                case.description
+            );
+            assert_eq!(
+                case.point.wrapping_offset(case.offset, size),
+                case.wrapping,
+                "{}",
+                case.description
+            );
+        }
+
+        assert_eq!(Point2::new(-1, 2).unsigned(), None);
+        assert_eq!(Point2::new(1, 2).unsigned(), Some(Point2::new(1, 2)));
+        assert_eq!(Point2::new(1_usize, 2).signed(), Point2::new(1, 2));
+    }
+
+    #[test]
+    fn test_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.offset(), -direction.reverse().offset());
+            assert_eq!(Direction::from_offset(direction.offset()), Some(direction));
+            assert_eq!(Compass::from(direction).offset(), direction.offset());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::from_offset(Point2::new(1, 1)), None);
+
+        for compass in Compass::ALL {
+            assert_eq!(compass.turn_right().turn_left(), compass);
+            assert_eq!(compass.offset(), -compass.reverse().offset());
+            assert_eq!(Compass::from_offset(compass.offset()), Some(compass));
+            assert_eq!(
+                compass.is_diagonal(),
+                compass.offset().manhattan(Point2::new(0, 0)) == 2
+            );
+        }
+        assert_eq!(Compass::North.turn_right(), Compass::NorthEast);
+        assert_eq!(Compass::North.turn_left(), Compass::NorthWest);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points: Vec<IPoint2> = vec![Point2::new(2, -1), Point2::new(-3, 4), Point2::new(0, 0)];
+        let bounds = BoundingBox::from_points(points).unwrap();
+        assert_eq!(bounds.min, Point2::new(-3, -1));
+        assert_eq!(bounds.max, Point2::new(2, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(bounds.contains(Point2::new(-3, 4)));
+        assert!(!bounds.contains(Point2::new(3, 0)));
+
+        assert_eq!(BoundingBox::<usize>::from_points(vec![]), None);
+        let mut bounds = BoundingBox::new(Point2::new(1_usize, 1));
+        bounds.include(Point2::new(3, 0));
+        assert_eq!((bounds.width(), bounds.height()), (3, 2));
+    }
+}
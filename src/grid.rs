@@ -0,0 +1,387 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Compass, Direction, IPoint2, UPoint2};
+use crate::parse::ParseError;
+
+// A cell's place in a grid, rows counting down from the top and columns across from the left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, column: usize) -> Position {
+        Position { row, column }
+    }
+}
+
+impl From<Position> for UPoint2 {
+    fn from(position: Position) -> UPoint2 {
+        UPoint2::new(position.column, position.row)
+    }
+}
+
+impl From<UPoint2> for Position {
+    fn from(point: UPoint2) -> Position {
+        Position::new(point.y, point.x)
+    }
+}
+
+// A rectangle of cells stored row by row in one allocation, so looking up a cell is
+// an index calculation rather than a hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        assert!(width > 0, "a grid needs at least one column");
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Parse character art, one row per line. `cell` turns each character into a cell,
+    // returning None for characters that don't belong, which are reported with `hint`.
+    pub fn parse(
+        day: u32,
+        input: &str,
+        hint: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            let line = input.lines().next().unwrap_or(input);
+            return Err(ParseError::at(
+                day,
+                input,
+                line,
+                "expected a row of the grid",
+            ));
+        }
+
+        let mut cells = Vec::with_capacity(width * input.lines().count());
+        for line in input.lines() {
+            if line.chars().count() != width {
+                let row_hint = format!("expected a row of {} cells, like the first row", width);
+                return Err(ParseError::at(day, input, line, row_hint));
+            }
+            for (offset, c) in line.char_indices() {
+                match cell(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let token = &line[offset..offset + c.len_utf8()];
+                        return Err(ParseError::at(day, input, token, hint));
+                    }
+                }
+            }
+        }
+        Ok(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.height && position.column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        match self.contains(position) {
+            true => Some(&self.cells[position.row * self.width + position.column]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.row * self.width + position.column]),
+            false => None,
+        }
+    }
+
+    // Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| Position::new(index / width, index % width))
+    }
+
+    // Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(row < self.height, "row {} is outside the grid", row);
+        self.cells[row * self.width..(row + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, column: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(column < self.width, "column {} is outside the grid", column);
+        self.cells[column..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    // The positions up, right, down and left of `position` that are inside the grid
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, Direction::ALL.into_iter().map(Direction::offset))
+    }
+
+    // The neighbours4 and then the diagonal positions that are inside the grid
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        let diagonals = Compass::ALL
+            .into_iter()
+            .map(Compass::offset)
+            .filter(|offset| offset.x != 0 && offset.y != 0);
+        let offsets = Direction::ALL.into_iter().map(Direction::offset);
+        self.offsets(position, offsets.chain(diagonals))
+    }
+
+    fn offsets(
+        &self,
+        position: Position,
+        offsets: impl Iterator<Item = IPoint2> + 'static,
+    ) -> impl Iterator<Item = Position> + '_ {
+        offsets.filter_map(move |offset| {
+            let neighbour = UPoint2::from(position).checked_offset(offset)?.into();
+            self.contains(neighbour).then_some(neighbour)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Render the grid with `cell` formatting each cell, one line per row
+    pub fn display<'a, D: fmt::Display>(
+        &'a self,
+        cell: impl Fn(Position, &T) -> D + 'a,
+    ) -> impl fmt::Display + 'a {
+        Render { grid: self, cell }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+}
+
+impl<T, F, D> fmt::Display for Render<'_, T, F>
+where
+    F: Fn(Position, &T) -> D,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, cell) in self.grid.iter() {
+            write!(f, "{}", (self.cell)(position, cell))?;
+            if position.column == self.grid.width - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(0, input, "expected a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse() {
+        struct TestCase {
+            description: &'static str,
+            input: &'static str,
+            expected: Result<(usize, usize), (usize, usize, &'static str)>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "rectangle",
+                input: "123\n456\n",
+                expected: Ok((3, 2)),
+            },
+            TestCase {
+                description: "single cell",
+                input: "7",
+                expected: Ok((1, 1)),
+            },
+            TestCase {
+                description: "empty",
+                input: "",
+                expected: Err((1, 1, "expected a row of the grid")),
+            },
+            TestCase {
+                description: "ragged",
+                input: "123\n45",
+                expected: Err((2, 1, "expected a row of 3 cells, like the first row")),
+            },
+            TestCase {
+                description: "bad cell",
+                input: "123\n4x6",
+                expected: Err((2, 2, "expected a digit")),
+            },
+        ];
+        for case in cases {
+            let got = digits(case.input)
+                .map(|grid| (grid.width(), grid.height()))
+                .map_err(|err| (err.line, err.column, err.hint.clone()));
+            let expected = case
+                .expected
+                .map_err(|(line, column, hint)| (line, column, hint.to_string()));
+            assert_eq!(got, expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut grid = digits("123\n456").unwrap();
+        assert_eq!(grid[Position::new(1, 2)], 6);
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+        assert_eq!(grid.get(Position::new(0, 3)), None);
+
+        grid[Position::new(0, 0)] = 9;
+        assert_eq!(grid.row(0).copied().collect::<Vec<u32>>(), vec![9, 2, 3]);
+        assert_eq!(
+            grid.row(1).rev().copied().collect::<Vec<u32>>(),
+            vec![6, 5, 4]
+        );
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(
+            grid.iter().nth(4),
+            Some((Position::new(1, 1), &5)),
+            "cells are in row order"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside the grid")]
+    fn test_row_outside_grid() {
+        digits("123\n456").unwrap().row(2).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the grid")]
+    fn test_column_outside_grid() {
+        digits("123\n456").unwrap().column(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid needs at least one column")]
+    fn test_new_without_columns() {
+        Grid::new(0, 2, ());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        struct TestCase {
+            description: &'static str,
+            position: Position,
+            expected4: usize,
+            expected8: usize,
+        }
+        let grid = Grid::new(3, 3, ());
+        let cases = vec![
+            TestCase {
+                description: "corner",
+                position: Position::new(0, 0),
+                expected4: 2,
+                expected8: 3,
+            },
+            TestCase {
+                description: "edge",
+                position: Position::new(1, 2),
+                expected4: 3,
+                expected8: 5,
+            },
+            TestCase {
+                description: "middle",
+                position: Position::new(1, 1),
+                expected4: 4,
+                expected8: 8,
+            },
+        ];
+        for case in cases {
+            let neighbours4: Vec<Position> = grid.neighbours4(case.position).collect();
+            let neighbours8: Vec<Position> = grid.neighbours8(case.position).collect();
+            assert_eq!(neighbours4.len(), case.expected4, "{}", case.description);
+            assert_eq!(neighbours8.len(), case.expected8, "{}", case.description);
+            assert_eq!(neighbours8[..neighbours4.len()], neighbours4[..]);
+            assert!(neighbours8
+                .iter()
+                .all(|&neighbour| grid.contains(neighbour)));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(
+            grid.display(|position, &cell| match position.row == position.column {
+                true => '#',
+                false => char::from_digit(cell, 10).unwrap(),
+            })
+            .to_string(),
+            "#23\n4#6\n"
+        );
+        assert_eq!(grid.map(|cell| cell % 2).to_string(), "101\n010\n");
+    }
+}
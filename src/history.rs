@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::bench::Measurement;
+
+pub const DEFAULT_HISTORY_FILE: &str = "bench-history.csv";
+
+const HEADER: &str = "timestamp,commit,year,day,part,variant,generator_ns,generator_spread_ns,solution_ns,solution_spread_ns,samples";
+
+// Histories written before the year was recorded only hold 2022's days
+const LEGACY_YEAR: &str = "2022";
+
+// A slowdown is only flagged when it is this many standard errors away from the
+// baseline, and at least this much slower, so noise and tiny changes are ignored
+const SIGNIFICANCE: f64 = 3.0;
+const MIN_CHANGE: f64 = 0.05;
+
+// One benchmarked part or variant from one run. The history is a CSV file with one
+// record per line, so runs can be appended and read back without rewriting the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    // Seconds since the Unix epoch, every record from one run shares it
+    pub timestamp: u64,
+    pub commit: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<String>,
+    pub generator: Measurement,
+    pub solution: Measurement,
+}
+
+impl Record {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.commit,
+            self.year,
+            self.day,
+            self.part,
+            self.variant.as_deref().unwrap_or(""),
+            self.generator.median.as_nanos(),
+            self.generator.spread.as_nanos(),
+            self.solution.median.as_nanos(),
+            self.solution.spread.as_nanos(),
+            self.solution.samples
+        )
+    }
+
+    fn from_csv(line: &str) -> Result<Record> {
+        let mut fields: Vec<&str> = line.split(',').collect();
+        if fields.len() == 10 {
+            fields.insert(2, LEGACY_YEAR);
+        }
+        if fields.len() != 11 {
+            return Err(anyhow!("expected 11 fields, found {}", fields.len()));
+        }
+        let number = |index: usize| {
+            fields[index]
+                .parse::<u64>()
+                .map_err(|_| anyhow!("expected a number, found {}", fields[index]))
+        };
+        let samples = number(10)? as usize;
+        Ok(Record {
+            timestamp: number(0)?,
+            commit: fields[1].to_string(),
+            year: number(2)? as u32,
+            day: number(3)? as u32,
+            part: number(4)? as u32,
+            variant: match fields[5] {
+                "" => None,
+                variant => Some(variant.to_string()),
+            },
+            generator: Measurement {
+                median: Duration::from_nanos(number(6)?),
+                spread: Duration::from_nanos(number(7)?),
+                samples,
+            },
+            solution: Measurement {
+                median: Duration::from_nanos(number(8)?),
+                spread: Duration::from_nanos(number(9)?),
+                samples,
+            },
+        })
+    }
+
+    pub fn name(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!(
+                "{} Day {} - Part {} - {}",
+                self.year, self.day, self.part, variant
+            ),
+            None => format!("{} Day {} - Part {}", self.year, self.day, self.part),
+        }
+    }
+}
+
+// Read every record in the history. A missing file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<Record>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).with_context(|| format!("could not read {}", path.display())),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("timestamp,"))
+        .map(|(index, line)| {
+            Record::from_csv(line).with_context(|| format!("{} line {}", path.display(), index + 1))
+        })
+        .collect()
+}
+
+// Append records to the history, writing the header first if the file is new
+pub fn append(path: &Path, records: &[Record]) -> Result<()> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("could not open {}", path.display()))?;
+    let mut contents = String::new();
+    if new {
+        contents.push_str(HEADER);
+        contents.push('\n');
+    }
+    for record in records {
+        contents.push_str(&record.to_csv());
+        contents.push('\n');
+    }
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("could not write {}", path.display()))
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+// The short hash of HEAD, marked dirty when the working tree has changes,
+// so timings of uncommitted work aren't mistaken for the commit's
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain"]) {
+            Some(status) if status.is_empty() => commit,
+            _ => format!("{}-dirty", commit),
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Slower,
+    Faster,
+    Unchanged,
+}
+
+#[derive(Debug)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline: Measurement,
+    pub latest: Measurement,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    // The relative change of the median, positive when the latest run is slower
+    pub fn change(&self) -> f64 {
+        relative_change(&self.baseline, &self.latest)
+    }
+}
+
+fn relative_change(baseline: &Measurement, latest: &Measurement) -> f64 {
+    let baseline_secs = baseline.median.as_secs_f64();
+    match baseline_secs == 0.0 {
+        true => 0.0,
+        false => (latest.median.as_secs_f64() - baseline_secs) / baseline_secs,
+    }
+}
+
+// Compare two medians using their spreads. The median absolute deviation is scaled to a
+// standard deviation (x1.4826), then to the standard error of a median (x1.2533 / sqrt(n)).
+pub fn verdict(baseline: &Measurement, latest: &Measurement) -> Verdict {
+    let standard_error = |measurement: &Measurement| {
+        1.4826 * 1.2533 * measurement.spread.as_secs_f64()
+            / (measurement.samples.max(1) as f64).sqrt()
+    };
+    let difference = latest.median.as_secs_f64() - baseline.median.as_secs_f64();
+    let error = standard_error(baseline).hypot(standard_error(latest));
+    let significant = difference.abs() > SIGNIFICANCE * error;
+    let change = relative_change(baseline, latest);
+
+    match (significant, change) {
+        (true, change) if change >= MIN_CHANGE => Verdict::Slower,
+        (true, change) if change <= -MIN_CHANGE => Verdict::Faster,
+        _ => Verdict::Unchanged,
+    }
+}
+
+type Key = (u32, u32, u32, Option<String>);
+
+// The most recent record of each part and variant, from the commits matching the filter
+fn latest_records<'a>(records: &'a [Record], commit: Option<&str>) -> HashMap<Key, &'a Record> {
+    let mut latest: HashMap<Key, &Record> = HashMap::new();
+    for record in records
+        .iter()
+        .filter(|record| commit.is_none_or(|commit| matches_commit(&record.commit, commit)))
+    {
+        let key = (record.year, record.day, record.part, record.variant.clone());
+        if latest
+            .get(&key)
+            .is_none_or(|existing| existing.timestamp <= record.timestamp)
+        {
+            latest.insert(key, record);
+        }
+    }
+    latest
+}
+
+// Commits can be given as any prefix of the recorded hash
+fn matches_commit(recorded: &str, wanted: &str) -> bool {
+    !wanted.is_empty() && recorded.starts_with(wanted)
+}
+
+// Compare the solution timings of the latest run of each part against the baseline commit.
+// The latest run is the newest record overall, or the newest from `against` when given.
+pub fn compare(
+    records: &[Record],
+    baseline: &str,
+    against: Option<&str>,
+) -> Result<Vec<Comparison>> {
+    let baselines = latest_records(records, Some(baseline));
+    if baselines.is_empty() {
+        return Err(anyhow!(
+            "no benchmark runs recorded for commit {}",
+            baseline
+        ));
+    }
+    let latest = latest_records(records, against);
+    if let Some(against) = against {
+        if latest.is_empty() {
+            return Err(anyhow!("no benchmark runs recorded for commit {}", against));
+        }
+    }
+
+    let mut comparisons: Vec<(Key, Comparison)> = baselines
+        .into_iter()
+        .filter_map(|(key, baseline)| {
+            let latest = latest.get(&key)?;
+            Some((
+                key,
+                Comparison {
+                    name: baseline.name(),
+                    baseline: baseline.solution,
+                    latest: latest.solution,
+                    verdict: verdict(&baseline.solution, &latest.solution),
+                },
+            ))
+        })
+        .collect();
+    comparisons.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(comparisons
+        .into_iter()
+        .map(|(_, comparison)| comparison)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(median_micros: u64, spread_micros: u64) -> Measurement {
+        Measurement {
+            median: Duration::from_micros(median_micros),
+            spread: Duration::from_micros(spread_micros),
+            samples: 30,
+        }
+    }
+
+    fn record(
+        timestamp: u64,
+        commit: &str,
+        variant: Option<&str>,
+        solution: Measurement,
+    ) -> Record {
+        Record {
+            timestamp,
+            commit: commit.to_string(),
+            year: 2022,
+            day: 8,
+            part: 2,
+            variant: variant.map(String::from),
+            generator: measurement(300, 3),
+            solution,
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let records = vec![
+            record(1670000000, "abc1234", None, measurement(1716, 20)),
+            record(
+                1670000000,
+                "abc1234-dirty",
+                Some("two_iterators_per_loc"),
+                measurement(17000, 150),
+            ),
+        ];
+        for record in records {
+            assert_eq!(Record::from_csv(&record.to_csv()).unwrap(), record);
+        }
+
+        // Written before the year was recorded
+        assert_eq!(
+            Record::from_csv("1670000000,abc1234,8,2,,300000,3000,1716000,20000,30").unwrap(),
+            record(1670000000, "abc1234", None, measurement(1716, 20))
+        );
+
+        assert!(Record::from_csv("1670000000,abc1234,8,2").is_err());
+        assert!(Record::from_csv("soon,abc1234,8,2,,1,1,1,1,30").is_err());
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-history-test-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert!(load(&path).unwrap().is_empty());
+
+        let first = record(1, "aaaaaaa", None, measurement(100, 1));
+        let second = record(2, "bbbbbbb", None, measurement(120, 1));
+        append(&path, std::slice::from_ref(&first)).unwrap();
+        append(&path, std::slice::from_ref(&second)).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let loaded = load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.matches(HEADER).count(), 1);
+        assert_eq!(loaded.unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_verdict() {
+        struct TestCase {
+            description: &'static str,
+            baseline: Measurement,
+            latest: Measurement,
+            expected: Verdict,
+        }
+        let cases = vec![
+            TestCase {
+                description: "much slower with little noise",
+                baseline: measurement(100, 1),
+                latest: measurement(150, 1),
+                expected: Verdict::Slower,
+            },
+            TestCase {
+                description: "much faster with little noise",
+                baseline: measurement(150, 1),
+                latest: measurement(100, 1),
+                expected: Verdict::Faster,
+            },
+            TestCase {
+                description: "slower but within the noise",
+                baseline: measurement(100, 40),
+                latest: measurement(130, 40),
+                expected: Verdict::Unchanged,
+            },
+            TestCase {
+                description: "significant but tiny change",
+                baseline: measurement(1000, 0),
+                latest: measurement(1010, 0),
+                expected: Verdict::Unchanged,
+            },
+            TestCase {
+                description: "same",
+                baseline: measurement(100, 5),
+                latest: measurement(100, 5),
+                expected: Verdict::Unchanged,
+            },
+        ];
+        for case in cases {
+            let got = verdict(&case.baseline, &case.latest);
+            assert_eq!(got, case.expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let records = vec![
+            record(1, "aaaaaaa", None, measurement(100, 1)),
+            record(
+                1,
+                "aaaaaaa",
+                Some("two_iterators_per_loc"),
+                measurement(1000, 1),
+            ),
+            record(2, "bbbbbbb", None, measurement(200, 1)),
+            record(
+                2,
+                "bbbbbbb",
+                Some("two_iterators_per_loc"),
+                measurement(1000, 1),
+            ),
+            record(3, "ccccccc", None, measurement(100, 1)),
+        ];
+
+        let comparisons = compare(&records, "aaa", None).unwrap();
+        let got: Vec<(String, Verdict)> = comparisons
+            .iter()
+            .map(|comparison| (comparison.name.clone(), comparison.verdict))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("2022 Day 8 - Part 2".to_string(), Verdict::Unchanged),
+                (
+                    "2022 Day 8 - Part 2 - two_iterators_per_loc".to_string(),
+                    Verdict::Unchanged
+                ),
+            ]
+        );
+
+        let comparisons = compare(&records, "aaa", Some("bbbbbbb")).unwrap();
+        assert_eq!(comparisons[0].verdict, Verdict::Slower);
+        assert!((comparisons[0].change() - 1.0).abs() < 1e-9);
+
+        assert!(compare(&records, "ddd", None).is_err());
+        assert!(compare(&records, "aaa", Some("ddd")).is_err());
+    }
+}
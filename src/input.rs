@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// Environment variable that overrides the directory puzzle inputs are read from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(Source, io::Error),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(
+                f,
+                "input file {} does not exist (download it, or pass --input <PATH|->)",
+                path.display()
+            ),
+            InputError::Unreadable(source, err) => write!(f, "could not read {}: {}", source, err),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Missing(_) => None,
+            InputError::Unreadable(_, err) => Some(err),
+        }
+    }
+}
+
+impl Source {
+    // Decide where a day's input comes from. An explicit --input wins, then --input-dir,
+    // then the AOC_INPUT_DIR environment variable, then ./input
+    pub fn resolve(year: u32, day: u32, input: Option<&str>, input_dir: Option<&Path>) -> Source {
+        let env_dir = std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+        Source::resolve_with(year, day, input, input_dir, env_dir.as_deref())
+    }
+
+    fn resolve_with(
+        year: u32,
+        day: u32,
+        input: Option<&str>,
+        input_dir: Option<&Path>,
+        env_dir: Option<&Path>,
+    ) -> Source {
+        match input {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None => {
+                let dir = input_dir
+                    .or(env_dir)
+                    .unwrap_or_else(|| Path::new(DEFAULT_INPUT_DIR));
+                Source::File(dir.join(year.to_string()).join(format!("day{}.txt", day)))
+            }
+        }
+    }
+
+    // Read the input with Windows line endings turned into plain newlines, stripping
+    // the trailing newline like cargo-aoc did
+    pub fn read(&self) -> Result<String, InputError> {
+        let contents = match self {
+            Source::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|err| InputError::Unreadable(self.clone(), err))?;
+                contents
+            }
+            Source::File(path) => fs::read_to_string(path).map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => InputError::Missing(path.clone()),
+                _ => InputError::Unreadable(self.clone(), err),
+            })?,
+        };
+        Ok(contents
+            .replace("\r\n", "\n")
+            .trim_end_matches('\n')
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        struct TestCase {
+            description: &'static str,
+            input: Option<&'static str>,
+            input_dir: Option<&'static str>,
+            env_dir: Option<&'static str>,
+            expected: Source,
+        }
+        let cases = vec![
+            TestCase {
+                description: "default directory",
+                input: None,
+                input_dir: None,
+                env_dir: None,
+                expected: Source::File(PathBuf::from("input/2022/day7.txt")),
+            },
+            TestCase {
+                description: "environment directory",
+                input: None,
+                input_dir: None,
+                env_dir: Some("/tmp/env"),
+                expected: Source::File(PathBuf::from("/tmp/env/2022/day7.txt")),
+            },
+            TestCase {
+                description: "flag directory beats environment",
+                input: None,
+                input_dir: Some("/tmp/flag"),
+                env_dir: Some("/tmp/env"),
+                expected: Source::File(PathBuf::from("/tmp/flag/2022/day7.txt")),
+            },
+            TestCase {
+                description: "explicit file beats directories",
+                input: Some("samples/2022/day7.txt"),
+                input_dir: Some("/tmp/flag"),
+                env_dir: Some("/tmp/env"),
+                expected: Source::File(PathBuf::from("samples/2022/day7.txt")),
+            },
+            TestCase {
+                description: "stdin",
+                input: Some("-"),
+                input_dir: None,
+                env_dir: None,
+                expected: Source::Stdin,
+            },
+        ];
+
+        for case in cases {
+            let got = Source::resolve_with(
+                2022,
+                7,
+                case.input,
+                case.input_dir.map(Path::new),
+                case.env_dir.map(Path::new),
+            );
+            assert_eq!(got, case.expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        struct TestCase {
+            description: &'static str,
+            contents: &'static str,
+            expected: &'static str,
+        }
+        let cases = vec![
+            TestCase {
+                description: "trailing newlines",
+                contents: "1\n2\n\n",
+                expected: "1\n2",
+            },
+            TestCase {
+                description: "windows line endings",
+                contents: "1\r\n2\r\n\r\n",
+                expected: "1\n2",
+            },
+        ];
+        let path = std::env::temp_dir().join(format!("aoc-input-test-{}.txt", std::process::id()));
+        for case in cases {
+            fs::write(&path, case.contents).unwrap();
+            let got = Source::File(path.clone()).read();
+            fs::remove_file(&path).unwrap();
+            assert_eq!(got.unwrap(), case.expected, "{}", case.description);
+        }
+
+        let missing = PathBuf::from("does/not/exist/day1.txt");
+        match Source::File(missing.clone()).read() {
+            Err(InputError::Missing(path)) => assert_eq!(path, missing),
+            other => panic!("expected a missing input error, got {:?}", other),
+        }
+    }
+}
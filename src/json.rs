@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::answers;
+use crate::bench::Measurement;
+use crate::input::InputError;
+use crate::parse::ParseError;
+use crate::runner::Solver;
+use crate::solution::Answer;
+
+// The version of the record layout below. Adding a field keeps the version, while removing,
+// renaming or changing the meaning of one bumps it, so consumers can refuse what they
+// don't understand.
+pub const SCHEMA_VERSION: u32 = 1;
+
+// What run, check or bench made of one part or variant, printed as one JSON object per line.
+// Every field is always present, null when it doesn't apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub schema: u32,
+    // run, check or bench
+    pub command: &'static str,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    // The sample file the part was run on, or null for the puzzle input
+    pub sample: Option<String>,
+    // A number, or a string for answers made of letters
+    pub answer: Option<Answer>,
+    // Only checked by check
+    pub status: Option<Status>,
+    pub expected: Option<Answer>,
+    // Nanoseconds spent parsing the input and solving the part. For bench these are the
+    // medians, with the median absolute deviations in the spreads.
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub parse_spread_ns: Option<u64>,
+    pub solve_spread_ns: Option<u64>,
+    pub error: Option<Error>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    // The offending line of the input, pointed at, for parse errors
+    pub diagnostic: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    // The input couldn't be read
+    Input,
+    Parse,
+    Solve,
+    Panic,
+}
+
+impl Error {
+    // Solvers report parse errors as ParseErrors, anything else went wrong solving
+    pub fn from_anyhow(err: &anyhow::Error) -> Error {
+        match err.downcast_ref::<ParseError>() {
+            Some(parse) => Error {
+                kind: ErrorKind::Parse,
+                message: format!("{:#}", err),
+                diagnostic: Some(parse.diagnostic()),
+            },
+            None => Error {
+                kind: ErrorKind::Solve,
+                message: format!("{:#}", err),
+                diagnostic: None,
+            },
+        }
+    }
+
+    pub fn input(err: &InputError) -> Error {
+        Error {
+            kind: ErrorKind::Input,
+            message: err.to_string(),
+            diagnostic: None,
+        }
+    }
+
+    pub fn panic(message: String) -> Error {
+        Error {
+            kind: ErrorKind::Panic,
+            message,
+            diagnostic: None,
+        }
+    }
+
+    // The error as the text output shows it, the diagnostic if there is one
+    pub fn text(&self) -> &str {
+        self.diagnostic.as_deref().unwrap_or(&self.message)
+    }
+}
+
+impl Record {
+    // A record of `solver` with nothing filled in yet
+    pub fn new(command: &'static str, solver: &Solver, sample: Option<String>) -> Record {
+        Record {
+            schema: SCHEMA_VERSION,
+            command,
+            year: solver.year,
+            day: solver.day,
+            part: solver.part,
+            variant: solver.variant,
+            sample,
+            answer: None,
+            status: None,
+            expected: None,
+            parse_ns: None,
+            solve_ns: None,
+            parse_spread_ns: None,
+            solve_spread_ns: None,
+            error: None,
+        }
+    }
+
+    pub fn with_timing(self, parse: Duration, solve: Duration) -> Record {
+        Record {
+            parse_ns: Some(nanos(parse)),
+            solve_ns: Some(nanos(solve)),
+            ..self
+        }
+    }
+
+    pub fn with_measurements(self, parse: &Measurement, solve: &Measurement) -> Record {
+        Record {
+            parse_ns: Some(nanos(parse.median)),
+            solve_ns: Some(nanos(solve.median)),
+            parse_spread_ns: Some(nanos(parse.spread)),
+            solve_spread_ns: Some(nanos(solve.spread)),
+            ..self
+        }
+    }
+
+    pub fn with_check(self, status: &answers::Status) -> Record {
+        let (status, expected) = match status {
+            answers::Status::Pass => (Status::Pass, self.answer.clone()),
+            answers::Status::Fail { expected } => (Status::Fail, Some(expected.clone())),
+            answers::Status::Missing => (Status::Missing, None),
+        };
+        Record {
+            status: Some(status),
+            expected,
+            ..self
+        }
+    }
+
+    pub fn with_error(self, error: Error) -> Record {
+        Record {
+            error: Some(error),
+            ..self
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records only hold plain data")
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    #[test]
+    fn test_to_json() {
+        let solvers = crate::solvers();
+        let solver = solvers
+            .iter()
+            .find(|solver| (solver.day, solver.part, solver.variant) == (12, 1, Some("astar")))
+            .unwrap();
+
+        struct TestCase {
+            description: &'static str,
+            record: Record,
+            expected: &'static str,
+        }
+        let cases = vec![
+            TestCase {
+                description: "run",
+                record: Record {
+                    answer: Some(Answer::Number(31)),
+                    ..Record::new("run", solver, None)
+                }
+                .with_timing(Duration::from_micros(7), Duration::from_nanos(31_520)),
+                expected: r#"{"schema":1,"command":"run","year":2022,"day":12,"part":1,"variant":"astar","sample":null,"answer":31,"status":null,"expected":null,"parse_ns":7000,"solve_ns":31520,"parse_spread_ns":null,"solve_spread_ns":null,"error":null}"#,
+            },
+            TestCase {
+                description: "failed check of a text answer",
+                record: Record {
+                    answer: Some(Answer::Text("CMZ".to_string())),
+                    ..Record::new("check", solver, Some("samples/2022/day12.txt".to_string()))
+                }
+                .with_check(&answers::Status::Fail {
+                    expected: Answer::Text("MCD".to_string()),
+                }),
+                expected: r#"{"schema":1,"command":"check","year":2022,"day":12,"part":1,"variant":"astar","sample":"samples/2022/day12.txt","answer":"CMZ","status":"fail","expected":"MCD","parse_ns":null,"solve_ns":null,"parse_spread_ns":null,"solve_spread_ns":null,"error":null}"#,
+            },
+            TestCase {
+                description: "bench",
+                record: Record::new("bench", solver, None).with_measurements(
+                    &Measurement {
+                        median: Duration::from_nanos(1300),
+                        spread: Duration::from_nanos(20),
+                        samples: 30,
+                    },
+                    &Measurement {
+                        median: Duration::from_nanos(21_840),
+                        spread: Duration::from_nanos(400),
+                        samples: 30,
+                    },
+                ),
+                expected: r#"{"schema":1,"command":"bench","year":2022,"day":12,"part":1,"variant":"astar","sample":null,"answer":null,"status":null,"expected":null,"parse_ns":1300,"solve_ns":21840,"parse_spread_ns":20,"solve_spread_ns":400,"error":null}"#,
+            },
+            TestCase {
+                description: "panic",
+                record: Record::new("run", solver, None)
+                    .with_error(Error::panic("index out of bounds".to_string())),
+                expected: r#"{"schema":1,"command":"run","year":2022,"day":12,"part":1,"variant":"astar","sample":null,"answer":null,"status":null,"expected":null,"parse_ns":null,"solve_ns":null,"parse_spread_ns":null,"solve_spread_ns":null,"error":{"kind":"panic","message":"index out of bounds","diagnostic":null}}"#,
+            },
+        ];
+        for case in cases {
+            assert_eq!(case.record.to_json(), case.expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let solvers = runner::solvers(crate::YEARS);
+        let solver = solvers.iter().find(|solver| solver.day == 12).unwrap();
+        let err = solver.generate("Sabc\nxyz").unwrap_err();
+        let error = Error::from_anyhow(&err);
+        assert_eq!(error.kind, ErrorKind::Parse);
+        assert!(error.diagnostic.is_some(), "{:?}", error);
+
+        let error = Error::from_anyhow(&anyhow::anyhow!("no path to the end"));
+        assert_eq!(error.kind, ErrorKind::Solve);
+        assert_eq!(error.diagnostic, None);
+    }
+}
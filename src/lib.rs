@@ -1,32 +1,35 @@
-extern crate aoc_runner;
-
-#[macro_use]
-extern crate aoc_runner_derive;
-
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day8;
-// pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-// pub mod day14;
-// pub mod day15;
-// pub mod day16;
-// pub mod day17;
-// pub mod day18;
-// pub mod day19;
-// pub mod day20;
-// pub mod day21;
-// pub mod day22;
-// pub mod day23;
-// pub mod day24;
-// pub mod day25;
+use runner::{Solver, Year};
+
+pub mod answers;
+pub mod bench;
+pub mod crosscheck;
+pub mod fetch;
+#[cfg(test)]
+pub mod fixture;
+pub mod fuzz;
+pub mod gen;
+pub mod geom;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod json;
+pub mod mem;
+pub mod parse;
+pub mod pool;
+#[cfg(test)]
+pub mod property;
+pub mod runner;
+pub mod samples;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod summary;
+pub mod trace;
+#[cfg(target_os = "linux")]
+pub mod watch;
+
+pub mod year2022;
 
 pub static ASCII_DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
 
@@ -40,4 +43,15 @@ pub static ASCII_UPPERCASE: [char; 26] = [
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
-aoc_lib! { year = 2022 }
+// Every year with solved days, in order. Years are added here by `scaffold`
+pub static YEARS: &[Year] = &[Year::new(year2022::YEAR, year2022::SOLUTIONS)];
+
+// Every registered part and variant, in year then day order
+pub fn solvers() -> Vec<Solver> {
+    runner::solvers(YEARS)
+}
+
+// The year commands default to when none is given
+pub fn latest_year() -> u32 {
+    YEARS.last().map_or(year2022::YEAR, |year| year.year)
+}
@@ -1,6 +1,1432 @@
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
 
-use aoc_runner_derive::aoc_main;
+use anyhow::{anyhow, Result};
 
-aoc_main! { lib = advent_of_code_2022 }
\ No newline at end of file
+use advent_of_code::answers::{Answers, Status, DEFAULT_ANSWERS_FILE};
+use advent_of_code::bench::{self, Row, Settings, README};
+use advent_of_code::crosscheck::{self, panic_message};
+use advent_of_code::fetch::{self, Config, Fetched};
+use advent_of_code::gen;
+use advent_of_code::history::{self, Record, Verdict, DEFAULT_HISTORY_FILE};
+use advent_of_code::input::{InputError, Source};
+use advent_of_code::json;
+use advent_of_code::mem::{self, Profile};
+use advent_of_code::parse::ParseError;
+use advent_of_code::pool;
+use advent_of_code::runner::{Selection, Solver};
+use advent_of_code::samples::{self, Sample, SAMPLES_DIR};
+use advent_of_code::scaffold::{self, Change};
+use advent_of_code::solution::Answer;
+use advent_of_code::submit::{self, Outcome, DEFAULT_SUBMISSIONS_FILE};
+use advent_of_code::summary;
+use advent_of_code::trace;
+
+const USAGE: &str = "\
+Usage: advent-of-code <COMMAND> [OPTIONS]
+
+Commands:
+    run         Run the selected solutions and print their answers
+    bench       Benchmark the selected solutions
+    list        List the registered solutions
+    check       Compare the selected solutions against the recorded answers
+    crosscheck  Check that every variant of a part agrees, on the real and generated inputs
+    gen         Print a generated input for --day, from --seed and --size
+    compare     Compare the latest benchmark of each part against a baseline commit
+    scaffold    Create the module, sample stub, fuzz target and registry entries for a new --day
+    fetch       Download the input for --day, or every registered day, unless already saved
+    submit      Solve --day and --part, and submit the answer unless it is known to be wrong
+    watch       Rebuild and re-run --day on its samples and input whenever they or its module change
+
+Options:
+    --year <N>           Only select year N (scaffold and fetch --day default to the latest year)
+    --day <N>            Only select day N
+    --part <N>           Only select part N
+    --variant <NAME>     Only select the named variant
+    --input <PATH|->     Read the input from PATH, or from stdin with -
+    --input-dir <DIR>    Read and fetch inputs in DIR/YEAR/dayN.txt (default: $AOC_INPUT_DIR, then ./input)
+    --answers <PATH>     Read expected answers for check from PATH (default: answers.toml)
+    --samples            Use the sample inputs in samples/YEAR instead of the puzzle inputs
+    --seed <N>           Seed for the inputs crosscheck and gen generate (default: 0)
+    --size <N>           Roughly how large an input gen generates (default: 10)
+    --readme             Rewrite the benchmark table in Readme.md with the results of bench
+    --history <PATH>     Record and compare benchmarks in PATH (default: bench-history.csv)
+    --baseline <COMMIT>  Commit to compare against, as recorded in the history
+    --against <COMMIT>   Compare this commit instead of the latest run of each part
+    --log <PATH>         Record submitted answers in PATH (default: submissions.csv)
+    --trace <SPEC>       Trace solutions to stderr, like day11=debug or info,day12=trace
+    --format <FORMAT>    Print run, check and bench results as text or json, one object per line
+    --all                Run every selected part on a pool of threads and summarise them (run only)
+    --profile-mem        Count what run and bench allocate (needs --features profile-mem)
+";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run,
+    Bench,
+    List,
+    Check,
+    Crosscheck,
+    Gen,
+    Compare,
+    Scaffold,
+    Fetch,
+    Submit,
+    Watch,
+}
+
+// How run, check and bench print their results. JSON is one json::Record per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    command: Command,
+    selection: Selection,
+    input: Option<String>,
+    input_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    samples: bool,
+    seed: u64,
+    size: usize,
+    readme: bool,
+    history: Option<PathBuf>,
+    baseline: Option<String>,
+    against: Option<String>,
+    log: Option<PathBuf>,
+    trace: Option<String>,
+    profile_mem: bool,
+    all: bool,
+    format: Format,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("bench") => Command::Bench,
+        Some("list") => Command::List,
+        Some("check") => Command::Check,
+        Some("crosscheck") => Command::Crosscheck,
+        Some("gen") => Command::Gen,
+        Some("compare") => Command::Compare,
+        Some("scaffold") => Command::Scaffold,
+        Some("fetch") => Command::Fetch,
+        Some("submit") => Command::Submit,
+        Some("watch") => Command::Watch,
+        Some(other) => return Err(anyhow!("unknown command: {}", other)),
+        None => return Err(anyhow!("no command given")),
+    };
+
+    let mut selection = Selection::default();
+    let mut input = None;
+    let mut input_dir = None;
+    let mut answers = None;
+    let mut samples = false;
+    let mut seed = 0;
+    let mut size = 10;
+    let mut readme = false;
+    let mut history = None;
+    let mut baseline = None;
+    let mut against = None;
+    let mut log = None;
+    let mut trace = None;
+    let mut profile_mem = false;
+    let mut all = false;
+    let mut format = Format::Text;
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow!("{} requires a value", flag))
+        };
+        match flag.as_str() {
+            "--year" => selection.year = Some(parse_number(&value()?)?),
+            "--day" => selection.day = Some(parse_number(&value()?)?),
+            "--part" => selection.part = Some(parse_number(&value()?)?),
+            "--variant" => selection.variant = Some(value()?),
+            "--input" => input = Some(value()?),
+            "--input-dir" => input_dir = Some(PathBuf::from(value()?)),
+            "--answers" => answers = Some(PathBuf::from(value()?)),
+            "--samples" => samples = true,
+            "--readme" => readme = true,
+            "--history" => history = Some(PathBuf::from(value()?)),
+            "--baseline" => baseline = Some(value()?),
+            "--against" => against = Some(value()?),
+            "--log" => log = Some(PathBuf::from(value()?)),
+            "--trace" => trace = Some(value()?),
+            "--profile-mem" => profile_mem = true,
+            "--all" => all = true,
+            "--format" => {
+                format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(anyhow!("unknown format: {}", other)),
+                }
+            }
+            "--seed" => {
+                let value = value()?;
+                seed = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("expected a number, found {}", value))?
+            }
+            "--size" => size = parse_number(&value()?)? as usize,
+            _ => return Err(anyhow!("unknown option: {}", flag)),
+        }
+    }
+
+    Ok(Args {
+        command,
+        selection,
+        input,
+        input_dir,
+        answers,
+        samples,
+        seed,
+        size,
+        readme,
+        history,
+        baseline,
+        against,
+        log,
+        trace,
+        profile_mem,
+        all,
+        format,
+    })
+}
+
+fn parse_number(value: &str) -> Result<u32> {
+    value
+        .parse::<u32>()
+        .map_err(|_| anyhow!("expected a number, found {}", value))
+}
+
+// Keyed by year and day
+type Inputs = HashMap<(u32, u32), Result<String, InputError>>;
+
+// Load each selected day's input once, keeping the error for days whose input is unavailable
+fn load_inputs(
+    solvers: &[&Solver],
+    input: Option<&str>,
+    input_dir: Option<&Path>,
+) -> Result<Inputs> {
+    let mut days: Vec<(u32, u32)> = solvers
+        .iter()
+        .map(|solver| (solver.year, solver.day))
+        .collect();
+    days.dedup();
+    if input.is_some() && days.len() > 1 {
+        return Err(anyhow!(
+            "--input can only be used when a single day is selected"
+        ));
+    }
+    Ok(days
+        .into_iter()
+        .map(|(year, day)| {
+            let input = Source::resolve(year, day, input, input_dir).read();
+            ((year, day), input)
+        })
+        .collect())
+}
+
+// Inputs already on disk are never downloaded again, so the token is only needed for new days
+fn fetch_inputs(days: &[(u32, u32)], input_dir: Option<&Path>) -> Result<()> {
+    let config = Config::load()?;
+    for &(year, day) in days {
+        let Source::File(path) = Source::resolve(year, day, None, input_dir) else {
+            unreachable!("inputs without --input are files")
+        };
+        let (action, path) = match fetch::fetch(&config, year, day, &path)? {
+            Fetched::Cached(path) => ("cached", path),
+            Fetched::Downloaded(path) => ("fetched", path),
+        };
+        println!("{:>9} {}", action, path.display());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn watch(year: u32, day: u32, input: Option<&str>, input_dir: Option<&Path>) -> Result<()> {
+    advent_of_code::watch::watch(year, day, input, input_dir)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(_year: u32, _day: u32, _input: Option<&str>, _input_dir: Option<&Path>) -> Result<()> {
+    Err(anyhow!(
+        "watch uses inotify, which is only available on Linux"
+    ))
+}
+
+// Solve one part and submit its answer, true if it was right
+fn submit_answer(
+    solver: &Solver,
+    input: Option<&str>,
+    input_dir: Option<&Path>,
+    log: &Path,
+) -> Result<bool> {
+    let inputs = load_inputs(&[solver], input, input_dir)?;
+    let input = inputs[&(solver.year, solver.day)]
+        .as_ref()
+        .map_err(|err| anyhow!("{}", err))?;
+    let answer = solver
+        .run(input)
+        .map_err(|err| anyhow!("{}", describe(&err)))?
+        .answer;
+    println!("{} : {}", solver.name(), answer);
+
+    let config = Config::load()?;
+    let submission = submit::submit(
+        &config,
+        log,
+        solver.year,
+        solver.day,
+        solver.part,
+        &answer,
+        history::now(),
+    )?;
+    let reply = submission.reply;
+    match reply.outcome {
+        outcome if outcome.is_wrong() => {
+            println!("{}, wait {}s before trying again", outcome, reply.wait)
+        }
+        outcome => println!("{}", outcome),
+    }
+    Ok(reply.outcome == Outcome::Correct)
+}
+
+// The sample file a record was made from, as the JSON output names it
+fn sample_name(sample: Option<&Path>) -> Option<String> {
+    sample.map(|path| path.display().to_string())
+}
+
+// Parse errors get the offending line with a caret under it, anything else its chain of causes
+fn describe(err: &anyhow::Error) -> String {
+    match err.downcast_ref::<ParseError>() {
+        Some(err) => err.diagnostic(),
+        None => format!("{:#}", err),
+    }
+}
+
+fn run(
+    solvers: &[&Solver],
+    inputs: &Inputs,
+    profile_mem: bool,
+    format: Format,
+    sample: Option<&Path>,
+) -> bool {
+    // Silence the default hook, the panic message is reported with the part instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut ok = true;
+    for solver in solvers {
+        let record = json::Record::new("run", solver, sample_name(sample));
+        let record = match &inputs[&(solver.year, solver.day)] {
+            Ok(input) => match panic::catch_unwind(AssertUnwindSafe(|| solver.run(input))) {
+                Ok(Ok(run)) => {
+                    if format == Format::Text {
+                        println!("{} : {}", solver.name(), run.answer);
+                        println!("\tgenerator: {:?},", run.generator);
+                        println!("\trunner: {:?}", run.solution);
+                        if profile_mem {
+                            print_profile(
+                                &mem::profile(solver, input).map_err(|err| describe(&err)),
+                            );
+                        }
+                        println!();
+                    }
+                    json::Record {
+                        answer: Some(run.answer),
+                        ..record
+                    }
+                    .with_timing(run.generator, run.solution)
+                }
+                Ok(Err(err)) => {
+                    ok = false;
+                    record.with_error(json::Error::from_anyhow(&err))
+                }
+                Err(payload) => {
+                    ok = false;
+                    record.with_error(json::Error::panic(panic_message(payload.as_ref())))
+                }
+            },
+            Err(err) => {
+                ok = false;
+                record.with_error(json::Error::input(err))
+            }
+        };
+        match (format, &record.error) {
+            (Format::Json, _) => println!("{}", record.to_json()),
+            (Format::Text, Some(error)) => println!("{} : {}", solver.name(), error.text()),
+            (Format::Text, None) => {}
+        }
+    }
+
+    let _ = panic::take_hook();
+    ok
+}
+
+// Run every part on a pool of worker threads, each part timing its own generator, then
+// print one table of every answer checked against the answers database
+fn run_all(solvers: &[&Solver], inputs: &Inputs, answers: &Answers) -> bool {
+    // Silence the default hook, the panic message is reported in the table instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let workers = pool::workers();
+    let start = Instant::now();
+    let entries = pool::map(solvers, workers, |solver| {
+        let (status, answer, timing) = match &inputs[&(solver.year, solver.day)] {
+            Ok(input) => match panic::catch_unwind(AssertUnwindSafe(|| solver.run(input))) {
+                Ok(Ok(run)) => {
+                    let status =
+                        match answers.check(solver.year, solver.day, solver.part, &run.answer) {
+                            Status::Pass => "pass",
+                            Status::Fail { .. } => "FAIL",
+                            Status::Missing => "missing",
+                        };
+                    (
+                        status,
+                        run.answer.to_string(),
+                        Some((run.generator, run.solution)),
+                    )
+                }
+                Ok(Err(err)) => ("FAIL", describe(&err), None),
+                Err(payload) => ("FAIL", panic_message(payload.as_ref()), None),
+            },
+            Err(err @ InputError::Missing(_)) => ("missing", err.to_string(), None),
+            Err(err) => ("FAIL", err.to_string(), None),
+        };
+        summary::Entry {
+            name: solver.name(),
+            status,
+            answer,
+            timing,
+        }
+    });
+    let wall = start.elapsed();
+
+    let _ = panic::take_hook();
+    print!("{}", summary::report(&entries, wall, workers));
+    entries.iter().all(|entry| entry.status != "FAIL")
+}
+
+// Memory is counted in a run of its own after the timed ones. The counting allocator
+// is global in a profile-mem build though, so the timed runs pay for counting too.
+fn print_profile(profile: &Result<Profile, String>) {
+    match profile {
+        Ok(profile) => {
+            println!("\tgenerator memory: {},", profile.generator);
+            println!("\trunner memory: {}", profile.solution);
+        }
+        Err(err) => println!("\tmemory: {}", err),
+    }
+}
+
+// Benchmark each solver, timing each day's generator once and sharing it between its parts
+fn bench(
+    solvers: &[&Solver],
+    inputs: &Inputs,
+    profile_mem: bool,
+    format: Format,
+    sample: Option<&Path>,
+) -> Vec<Row> {
+    let settings = Settings::default();
+    let mut generators = HashMap::new();
+    let mut rows = vec![];
+    for solver in solvers {
+        let (generator, solution) = match &inputs[&(solver.year, solver.day)] {
+            Ok(input) => {
+                let generator = generators
+                    .entry((solver.year, solver.day))
+                    .or_insert_with(|| {
+                        bench::bench_generator(solver, input, &settings)
+                            .map_err(|err| json::Error::from_anyhow(&err))
+                    })
+                    .clone();
+                let solution = match &generator {
+                    Ok(_) => bench::bench_solution(solver, input, &settings)
+                        .map_err(|err| json::Error::from_anyhow(&err)),
+                    Err(err) => Err(err.clone()),
+                };
+                (generator, solution)
+            }
+            Err(err) => (Err(json::Error::input(err)), Err(json::Error::input(err))),
+        };
+
+        let memory = match (&inputs[&(solver.year, solver.day)], &solution) {
+            (Ok(input), Ok(_)) if profile_mem => {
+                Some(mem::profile(solver, input).map_err(|err| describe(&err)))
+            }
+            _ => None,
+        };
+
+        let record = json::Record::new("bench", solver, sample_name(sample));
+        match (&generator, &solution, format) {
+            (Ok(generator), Ok(solution), Format::Text) => {
+                println!("{}", solver.name());
+                println!("\tgenerator: {},", generator);
+                println!("\trunner: {}", solution);
+                if let Some(profile) = &memory {
+                    print_profile(profile);
+                }
+                println!();
+            }
+            (Err(err), _, Format::Text) | (_, Err(err), Format::Text) => {
+                println!("{} : {}", solver.name(), err.text())
+            }
+            (Ok(generator), Ok(solution), Format::Json) => {
+                println!(
+                    "{}",
+                    record.with_measurements(generator, solution).to_json()
+                )
+            }
+            (Err(err), _, Format::Json) | (_, Err(err), Format::Json) => {
+                println!("{}", record.with_error(err.clone()).to_json())
+            }
+        }
+        rows.push(Row {
+            year: solver.year,
+            day: solver.day,
+            part: solver.part,
+            variant: solver.variant,
+            generator: generator.map_err(|err| err.text().to_string()),
+            solution: solution.map_err(|err| err.text().to_string()),
+            memory,
+        });
+    }
+    rows
+}
+
+// Append the successful rows to the benchmark history under the current commit
+fn record_history(path: &Path, rows: &[Row]) -> Result<()> {
+    let timestamp = history::now();
+    let commit = history::current_commit();
+    let records: Vec<Record> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(Record {
+                timestamp,
+                commit: commit.clone(),
+                year: row.year,
+                day: row.day,
+                part: row.part,
+                variant: row.variant.map(String::from),
+                generator: *row.generator.as_ref().ok()?,
+                solution: *row.solution.as_ref().ok()?,
+            })
+        })
+        .collect();
+    if records.is_empty() {
+        return Ok(());
+    }
+    history::append(path, &records)?;
+    eprintln!(
+        "Recorded {} results for {} in {}",
+        records.len(),
+        commit,
+        path.display()
+    );
+    Ok(())
+}
+
+// Print how each part's latest benchmark compares with the baseline, failing on any slowdown
+fn compare(
+    path: &Path,
+    selection: &Selection,
+    baseline: &str,
+    against: Option<&str>,
+) -> Result<bool> {
+    let records: Vec<Record> = history::load(path)?
+        .into_iter()
+        .filter(|record| {
+            selection.matches_part(
+                record.year,
+                record.day,
+                record.part,
+                record.variant.as_deref(),
+            )
+        })
+        .collect();
+    let comparisons = history::compare(&records, baseline, against)?;
+
+    println!(
+        "{:<45} {:<18} {:<18} {:<8} Verdict",
+        "Solution", "Baseline", "Latest", "Change"
+    );
+    let mut slower = 0;
+    for comparison in &comparisons {
+        let verdict = match comparison.verdict {
+            Verdict::Slower => {
+                slower += 1;
+                "SLOWER"
+            }
+            Verdict::Faster => "faster",
+            Verdict::Unchanged => "unchanged",
+        };
+        println!(
+            "{:<45} {:<18} {:<18} {:<8} {}",
+            comparison.name,
+            comparison.baseline.to_string(),
+            comparison.latest.to_string(),
+            format!("{:+.1}%", comparison.change() * 100.0),
+            verdict
+        );
+    }
+    println!();
+    println!(
+        "{} compared, {} significantly slower than {}",
+        comparisons.len(),
+        slower,
+        baseline
+    );
+    Ok(slower == 0)
+}
+
+// Replace the benchmark table in the Readme with freshly measured rows
+fn write_readme(rows: &[Row]) -> Result<()> {
+    let readme = std::fs::read_to_string(README)?;
+    let readme = bench::update_readme(&readme, &bench::table(rows))?;
+    std::fs::write(README, readme)?;
+    eprintln!("Updated the benchmark table in {}", README);
+    Ok(())
+}
+
+// Compare each solver's answer with the expected one, which comes from the answers
+// database for puzzle inputs, or from the sample file itself for samples
+fn check(
+    solvers: &[&Solver],
+    inputs: &Inputs,
+    expect: &dyn Fn(&Solver, &Answer) -> Status,
+    format: Format,
+    sample: Option<&Path>,
+) -> bool {
+    // Silence the default hook, the panic message is reported in the table instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    if format == Format::Text {
+        println!(
+            "{:<45} {:<8} {:<20} Expected",
+            "Solution", "Status", "Answer"
+        );
+    }
+    for solver in solvers {
+        let record = json::Record::new("check", solver, sample_name(sample));
+        let failure = |error| json::Record {
+            status: Some(json::Status::Fail),
+            ..record.clone().with_error(error)
+        };
+        let ((status, answer, detail), record) = match &inputs[&(solver.year, solver.day)] {
+            Ok(input) => match panic::catch_unwind(AssertUnwindSafe(|| solver.run(input))) {
+                Ok(Ok(run)) => {
+                    let answer = run.answer.to_string();
+                    let checked = expect(solver, &run.answer);
+                    let record = json::Record {
+                        answer: Some(run.answer),
+                        ..record.clone()
+                    }
+                    .with_timing(run.generator, run.solution)
+                    .with_check(&checked);
+                    let text = match checked {
+                        Status::Pass => {
+                            passed += 1;
+                            ("pass", answer.clone(), answer)
+                        }
+                        Status::Fail { expected } => {
+                            failed += 1;
+                            ("FAIL", answer, expected.to_string())
+                        }
+                        Status::Missing => {
+                            missing += 1;
+                            ("missing", answer, "no expected answer".to_string())
+                        }
+                    };
+                    (text, record)
+                }
+                Ok(Err(err)) => {
+                    failed += 1;
+                    (
+                        ("FAIL", "-".to_string(), format!("{:#}", err)),
+                        failure(json::Error::from_anyhow(&err)),
+                    )
+                }
+                Err(payload) => {
+                    failed += 1;
+                    let message = panic_message(payload.as_ref());
+                    (
+                        ("FAIL", "-".to_string(), message.clone()),
+                        failure(json::Error::panic(message)),
+                    )
+                }
+            },
+            Err(err @ InputError::Missing(path)) => {
+                missing += 1;
+                (
+                    (
+                        "missing",
+                        "-".to_string(),
+                        format!("no input at {}", path.display()),
+                    ),
+                    json::Record {
+                        status: Some(json::Status::Missing),
+                        ..record.clone().with_error(json::Error::input(err))
+                    },
+                )
+            }
+            Err(err) => {
+                failed += 1;
+                (
+                    ("FAIL", "-".to_string(), err.to_string()),
+                    failure(json::Error::input(err)),
+                )
+            }
+        };
+        match format {
+            Format::Text => println!(
+                "{:<45} {:<8} {:<20} {}",
+                solver.name(),
+                status,
+                answer,
+                detail
+            ),
+            Format::Json => println!("{}", record.to_json()),
+        }
+    }
+
+    let _ = panic::take_hook();
+    if format == Format::Text {
+        println!();
+        println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    }
+    failed == 0
+}
+
+// Run every implementation of each selected part that has variants against each other
+fn crosscheck(solvers: &[Solver], selection: &Selection, inputs: &Inputs, seed: u64) -> bool {
+    let mut parts: Vec<(u32, u32, u32)> = solvers
+        .iter()
+        .filter(|solver| solver.variant.is_some() && selection.matches(solver))
+        .map(|solver| (solver.year, solver.day, solver.part))
+        .collect();
+    parts.dedup();
+    if parts.is_empty() {
+        println!("None of the selected parts have variants to compare");
+        return true;
+    }
+
+    // Silence the default hook, panics are reported as outcomes instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut ok = true;
+    for (year, day, part) in parts {
+        let implementations: Vec<&Solver> = solvers
+            .iter()
+            .filter(|solver| (solver.year, solver.day, solver.part) == (year, day, part))
+            .collect();
+        let real = inputs
+            .get(&(year, day))
+            .and_then(|input| input.as_deref().ok());
+        let generator = gen::generator(year, day);
+        let report = crosscheck::crosscheck(&implementations, real, generator, seed);
+
+        let name = implementations[0].name();
+        match report.disagreement {
+            None => {
+                println!(
+                    "{} : {} implementations agree on {} inputs{}",
+                    name,
+                    report.implementations.len(),
+                    report.agreed,
+                    match real {
+                        Some(_) => "",
+                        None => " (no real input)",
+                    }
+                );
+                if report.unsolved > 0 {
+                    println!(
+                        "\t{} inputs could not be solved by any implementation",
+                        report.unsolved
+                    );
+                }
+            }
+            Some(disagreement) => {
+                ok = false;
+                println!(
+                    "{} : implementations disagree on {}",
+                    name, disagreement.source
+                );
+                let width = report
+                    .implementations
+                    .iter()
+                    .map(String::len)
+                    .max()
+                    .unwrap_or(0);
+                for (implementation, outcome) in disagreement.outcomes {
+                    match outcome {
+                        Ok(answer) => {
+                            println!("\t{:<width$} : {}", implementation, answer, width = width)
+                        }
+                        Err(err) => println!(
+                            "\t{:<width$} : error: {}",
+                            implementation,
+                            err,
+                            width = width
+                        ),
+                    }
+                }
+                println!("Smallest input that disagrees:");
+                println!("{}", disagreement.input);
+            }
+        }
+        println!();
+    }
+
+    let _ = panic::take_hook();
+    ok
+}
+
+// Run a command once per sample file, against the solvers for that sample's day
+fn run_samples(
+    command: Command,
+    solvers: &[&Solver],
+    samples: &[Sample],
+    profile_mem: bool,
+    format: Format,
+) -> bool {
+    if samples.is_empty() {
+        eprintln!("error: no samples found in {}", SAMPLES_DIR);
+        return false;
+    }
+
+    let mut ok = true;
+    for sample in samples {
+        let solvers: Vec<&Solver> = solvers
+            .iter()
+            .filter(|solver| (solver.year, solver.day) == (sample.year, sample.day))
+            .copied()
+            .collect();
+        if solvers.is_empty() {
+            continue;
+        }
+
+        if format == Format::Text {
+            println!("# {}", sample.path.display());
+        }
+        let key = (sample.year, sample.day);
+        let inputs: Inputs = HashMap::from([(key, Ok(sample.input.clone()))]);
+        let path = Some(sample.path.as_path());
+        ok &= match command {
+            Command::Run => run(&solvers, &inputs, profile_mem, format, path),
+            Command::Bench => bench(&solvers, &inputs, profile_mem, format, path)
+                .iter()
+                .all(|row| row.solution.is_ok()),
+            Command::Check => check(
+                &solvers,
+                &inputs,
+                &|solver, answer| sample.check(solver.part, answer),
+                format,
+                path,
+            ),
+            Command::List
+            | Command::Crosscheck
+            | Command::Gen
+            | Command::Compare
+            | Command::Scaffold
+            | Command::Fetch
+            | Command::Submit
+            | Command::Watch => unreachable!(),
+        };
+        if format == Format::Text {
+            println!();
+        }
+    }
+    ok
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {:#}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(spec) = &args.trace {
+        // Benchmarks are built without tracing, and would only measure the tracing anyway
+        if args.command == Command::Bench {
+            eprintln!("error: bench cannot be traced");
+            return ExitCode::FAILURE;
+        }
+        if let Err(err) = trace::Filter::parse(spec).and_then(trace::init) {
+            eprintln!("error: --trace: {:#}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if args.format == Format::Json {
+        if !matches!(args.command, Command::Run | Command::Check | Command::Bench) {
+            eprintln!("error: --format json can only be used with run, check or bench");
+            return ExitCode::FAILURE;
+        }
+        if args.all || args.profile_mem {
+            eprintln!("error: --format json cannot be combined with --all or --profile-mem");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if args.all {
+        if args.command != Command::Run {
+            eprintln!("error: --all can only be used with run");
+            return ExitCode::FAILURE;
+        }
+        // Whole years are run at once, and only the real inputs can be checked
+        let narrowed = args.selection.day.is_some()
+            || args.selection.part.is_some()
+            || args.selection.variant.is_some();
+        if narrowed || args.input.is_some() || args.samples || args.profile_mem {
+            eprintln!(
+                "error: run --all can only be narrowed down by --year, and uses the real inputs"
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if args.profile_mem {
+        if !matches!(args.command, Command::Run | Command::Bench) {
+            eprintln!("error: --profile-mem can only be used with run or bench");
+            return ExitCode::FAILURE;
+        }
+        if !mem::ENABLED {
+            eprintln!("error: --profile-mem needs the counting allocator, build with --features profile-mem");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // A new day has nothing registered yet, so this comes before selecting solutions
+    if args.command == Command::Scaffold {
+        let Some(day) = args.selection.day else {
+            eprintln!("error: scaffold needs a --day");
+            return ExitCode::FAILURE;
+        };
+        let year = args
+            .selection
+            .year
+            .unwrap_or_else(advent_of_code::latest_year);
+        return match scaffold::scaffold(Path::new("."), year, day) {
+            Ok(changes) => {
+                for change in changes {
+                    let action = match change {
+                        Change::Created(_) => "created",
+                        Change::Updated(_) => "updated",
+                        Change::Unchanged(_) => "unchanged",
+                    };
+                    println!("{:>9} {}", action, change.path().display());
+                }
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    // The day is rebuilt from its source, so it needn't be registered yet either
+    if args.command == Command::Watch {
+        let Some(day) = args.selection.day else {
+            eprintln!("error: watch needs a --day");
+            return ExitCode::FAILURE;
+        };
+        let year = args
+            .selection
+            .year
+            .unwrap_or_else(advent_of_code::latest_year);
+        return match watch(year, day, args.input.as_deref(), args.input_dir.as_deref()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    // Generators are looked up by day, nothing needs solving
+    if args.command == Command::Gen {
+        let Some(day) = args.selection.day else {
+            eprintln!("error: gen needs a --day");
+            return ExitCode::FAILURE;
+        };
+        let year = args
+            .selection
+            .year
+            .unwrap_or_else(advent_of_code::latest_year);
+        let Some(generator) = gen::generator(year, day) else {
+            eprintln!("error: there is no generator for {} day {}", year, day);
+            return ExitCode::FAILURE;
+        };
+        println!("{}", generator(&mut gen::Rng::new(args.seed), args.size));
+        return ExitCode::SUCCESS;
+    }
+
+    // A new day's input is fetched before it is scaffolded, so --day needn't be registered
+    if args.command == Command::Fetch {
+        let days = match args.selection.day {
+            Some(day) => {
+                let year = args
+                    .selection
+                    .year
+                    .unwrap_or_else(advent_of_code::latest_year);
+                vec![(year, day)]
+            }
+            None => {
+                let mut days: Vec<(u32, u32)> = advent_of_code::solvers()
+                    .iter()
+                    .filter(|solver| args.selection.matches(solver))
+                    .map(|solver| (solver.year, solver.day))
+                    .collect();
+                days.dedup();
+                days
+            }
+        };
+        return match fetch_inputs(&days, args.input_dir.as_deref()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let solvers = advent_of_code::solvers();
+    let selected: Vec<&Solver> = solvers
+        .iter()
+        .filter(|solver| args.selection.matches(solver))
+        .collect();
+    if selected.is_empty() {
+        eprintln!("error: no solutions match the selection");
+        return ExitCode::FAILURE;
+    }
+
+    if args.command == Command::List {
+        for solver in selected {
+            println!("{}", solver.name());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if args.command == Command::Compare {
+        let path = args
+            .history
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_HISTORY_FILE));
+        let Some(baseline) = args.baseline else {
+            eprintln!("error: compare needs a --baseline commit");
+            return ExitCode::FAILURE;
+        };
+        return match compare(&path, &args.selection, &baseline, args.against.as_deref()) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if args.command == Command::Submit {
+        if args.selection.day.is_none() || args.selection.part.is_none() {
+            eprintln!("error: submit needs a --day and a --part");
+            return ExitCode::FAILURE;
+        }
+        // Without --variant, the part's main solution is the one whose answer is sent
+        let Some(solver) = selected
+            .iter()
+            .find(|solver| solver.variant == args.selection.variant.as_deref())
+        else {
+            eprintln!("error: no solution matches the selection");
+            return ExitCode::FAILURE;
+        };
+        let log = args
+            .log
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SUBMISSIONS_FILE));
+        return match submit_answer(
+            solver,
+            args.input.as_deref(),
+            args.input_dir.as_deref(),
+            &log,
+        ) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if args.readme
+        && (args.command != Command::Bench
+            || args.samples
+            || args.input.is_some()
+            || args.selection != Selection::default())
+    {
+        eprintln!(
+            "error: --readme can only be used with bench, over every solution and the real inputs"
+        );
+        return ExitCode::FAILURE;
+    }
+
+    if args.samples {
+        if args.input.is_some() || args.input_dir.is_some() {
+            eprintln!("error: --samples cannot be combined with --input or --input-dir");
+            return ExitCode::FAILURE;
+        }
+        if args.command == Command::Crosscheck {
+            eprintln!("error: crosscheck cannot be used with --samples");
+            return ExitCode::FAILURE;
+        }
+        let ok = match samples::find(
+            Path::new(SAMPLES_DIR),
+            args.selection.year,
+            args.selection.day,
+        ) {
+            Ok(samples) => run_samples(
+                args.command,
+                &selected,
+                &samples,
+                args.profile_mem,
+                args.format,
+            ),
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                false
+            }
+        };
+        return match ok {
+            true => ExitCode::SUCCESS,
+            false => ExitCode::FAILURE,
+        };
+    }
+
+    let inputs = match load_inputs(&selected, args.input.as_deref(), args.input_dir.as_deref()) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let ok = match args.command {
+        Command::Run if args.all => {
+            let path = args
+                .answers
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_FILE));
+            match Answers::load(&path) {
+                Ok(answers) => run_all(&selected, &inputs, &answers),
+                Err(err) => {
+                    eprintln!("error: {:#}", err);
+                    false
+                }
+            }
+        }
+        Command::Run => run(&selected, &inputs, args.profile_mem, args.format, None),
+        Command::Bench => {
+            let rows = bench(&selected, &inputs, args.profile_mem, args.format, None);
+            let mut ok = rows.iter().all(|row| row.solution.is_ok());
+            let path = args
+                .history
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_HISTORY_FILE));
+            if mem::ENABLED {
+                eprintln!(
+                    "warning: not recording the benchmark history, timings from a profile-mem build include counting allocations"
+                );
+            } else if let Err(err) = record_history(&path, &rows) {
+                eprintln!("error: could not record the benchmark history: {:#}", err);
+                ok = false;
+            }
+            match args.readme {
+                true => match write_readme(&rows) {
+                    Ok(()) => ok,
+                    Err(err) => {
+                        eprintln!("error: could not update {}: {:#}", README, err);
+                        false
+                    }
+                },
+                false => ok,
+            }
+        }
+        Command::Check => {
+            let path = args
+                .answers
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_FILE));
+            match Answers::load(&path) {
+                Ok(answers) => check(
+                    &selected,
+                    &inputs,
+                    &|solver, answer| answers.check(solver.year, solver.day, solver.part, answer),
+                    args.format,
+                    None,
+                ),
+                Err(err) => {
+                    eprintln!("error: {:#}", err);
+                    false
+                }
+            }
+        }
+        Command::Crosscheck => crosscheck(&solvers, &args.selection, &inputs, args.seed),
+        Command::List
+        | Command::Gen
+        | Command::Compare
+        | Command::Scaffold
+        | Command::Fetch
+        | Command::Submit
+        | Command::Watch => {
+            unreachable!()
+        }
+    };
+
+    match ok {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Args with nothing but the command set, for cases to override
+    fn args(command: Command) -> Args {
+        Args {
+            command,
+            selection: Selection::default(),
+            input: None,
+            input_dir: None,
+            answers: None,
+            samples: false,
+            seed: 0,
+            size: 10,
+            readme: false,
+            history: None,
+            baseline: None,
+            against: None,
+            log: None,
+            trace: None,
+            profile_mem: false,
+            all: false,
+            format: Format::Text,
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        struct TestCase {
+            description: &'static str,
+            input: &'static str,
+            expected: Option<Args>,
+        }
+        let cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "bare command",
+                input: "list",
+                expected: Some(args(Command::List)),
+            },
+            TestCase {
+                description: "all flags",
+                input:
+                    "run --day 8 --part 2 --variant two_iterators_per_loc --input - --input-dir in",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: None,
+                        day: Some(8),
+                        part: Some(2),
+                        variant: Some("two_iterators_per_loc".to_string()),
+                    },
+                    input: Some("-".to_string()),
+                    input_dir: Some(PathBuf::from("in")),
+                    ..args(Command::Run)
+                }),
+            },
+            TestCase {
+                description: "answers file",
+                input: "check --answers answers/2022.toml",
+                expected: Some(Args {
+                    answers: Some(PathBuf::from("answers/2022.toml")),
+                    ..args(Command::Check)
+                }),
+            },
+            TestCase {
+                description: "samples",
+                input: "check --day 6 --samples",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: None,
+                        day: Some(6),
+                        part: None,
+                        variant: None,
+                    },
+                    samples: true,
+                    ..args(Command::Check)
+                }),
+            },
+            TestCase {
+                description: "scaffold",
+                input: "scaffold --year 2023 --day 14",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: Some(2023),
+                        day: Some(14),
+                        part: None,
+                        variant: None,
+                    },
+                    ..args(Command::Scaffold)
+                }),
+            },
+            TestCase {
+                description: "fetch",
+                input: "fetch --input-dir inputs",
+                expected: Some(Args {
+                    input_dir: Some(PathBuf::from("inputs")),
+                    ..args(Command::Fetch)
+                }),
+            },
+            TestCase {
+                description: "submit",
+                input: "submit --day 3 --part 2 --log log.csv",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: None,
+                        day: Some(3),
+                        part: Some(2),
+                        variant: None,
+                    },
+                    log: Some(PathBuf::from("log.csv")),
+                    ..args(Command::Submit)
+                }),
+            },
+            TestCase {
+                description: "trace",
+                input: "run --day 11 --trace day11=debug,day12=trace",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: None,
+                        day: Some(11),
+                        part: None,
+                        variant: None,
+                    },
+                    trace: Some("day11=debug,day12=trace".to_string()),
+                    ..args(Command::Run)
+                }),
+            },
+            TestCase {
+                description: "run all",
+                input: "run --all --year 2022",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: Some(2022),
+                        day: None,
+                        part: None,
+                        variant: None,
+                    },
+                    all: true,
+                    ..args(Command::Run)
+                }),
+            },
+            TestCase {
+                description: "json",
+                input: "check --format json",
+                expected: Some(Args {
+                    format: Format::Json,
+                    ..args(Command::Check)
+                }),
+            },
+            TestCase {
+                description: "unknown format",
+                input: "check --format yaml",
+                expected: None,
+            },
+            TestCase {
+                description: "watch",
+                input: "watch --day 12",
+                expected: Some(Args {
+                    selection: Selection {
+                        year: None,
+                        day: Some(12),
+                        part: None,
+                        variant: None,
+                    },
+                    ..args(Command::Watch)
+                }),
+            },
+            TestCase {
+                description: "crosscheck seed",
+                input: "crosscheck --seed 42",
+                expected: Some(Args {
+                    seed: 42,
+                    ..args(Command::Crosscheck)
+                }),
+            },
+            TestCase {
+                description: "gen",
+                input: "gen --day 5 --seed 7 --size 3",
+                expected: Some(Args {
+                    selection: Selection {
+                        day: Some(5),
+                        ..Selection::default()
+                    },
+                    seed: 7,
+                    size: 3,
+                    ..args(Command::Gen)
+                }),
+            },
+            TestCase {
+                description: "readme",
+                input: "bench --readme --profile-mem",
+                expected: Some(Args {
+                    readme: true,
+                    profile_mem: true,
+                    ..args(Command::Bench)
+                }),
+            },
+            TestCase {
+                description: "compare",
+                input: "compare --baseline abc1234 --against def5678 --history history.csv",
+                expected: Some(Args {
+                    history: Some(PathBuf::from("history.csv")),
+                    baseline: Some("abc1234".to_string()),
+                    against: Some("def5678".to_string()),
+                    ..args(Command::Compare)
+                }),
+            },
+            TestCase {
+                description: "unknown command",
+                input: "solve",
+                expected: None,
+            },
+            TestCase {
+                description: "missing value",
+                input: "bench --day",
+                expected: None,
+            },
+            TestCase {
+                description: "invalid day",
+                input: "check --day twelve",
+                expected: None,
+            },
+        ];
+
+        for case in cases {
+            let got = parse_args(case.input.split_whitespace().map(String::from));
+            match (got, case.expected) {
+                (Ok(got), Some(expected)) => assert_eq!(got, expected, "{}", case.description),
+                (Err(_), Some(_)) => panic!("{}: got error, want no error", case.description),
+                (Ok(_), None) => panic!("{}: got no error, want error", case.description),
+                (Err(_), None) => {}
+            }
+        }
+    }
+}
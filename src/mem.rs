@@ -0,0 +1,198 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{anyhow, Result};
+
+use crate::runner::Solver;
+
+// False unless built with the `profile-mem` feature, which installs the counting allocator.
+// Counting costs a few atomic operations per allocation, so it stays out of normal builds.
+pub const ENABLED: bool = cfg!(feature = "profile-mem");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+// The system allocator, counting every allocation and the bytes it hands out
+pub struct Counting;
+
+#[cfg(feature = "profile-mem")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Counting::freed(layout.size());
+    }
+
+    // A reallocation counts as a new allocation of the new size that frees the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            Counting::allocated(new_size);
+            Counting::freed(layout.size());
+        }
+        new
+    }
+}
+
+// What some code allocated while it ran
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    // Every byte handed out, even if it was freed again
+    pub bytes: u64,
+    // The most bytes the code held at once, on top of what was live before it started
+    pub peak: u64,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} (peak {})",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+// Three significant figures in the largest binary unit that keeps the number above 1
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let decimals = match value {
+        v if v < 10.0 => 2,
+        v if v < 100.0 => 1,
+        _ => 0,
+    };
+    format!("{:.*} {}", decimals, value, units[unit])
+}
+
+// Run `f` and count what it allocated. The counters are shared by the whole process,
+// so nothing else should be allocating on another thread meanwhile.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let value = f();
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (value, usage)
+}
+
+// The memory one part uses, parsing and solving counted separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub generator: Usage,
+    pub solution: Usage,
+}
+
+// Parse and solve once, counting the allocations of each. The parsed input is dropped
+// after solving, so the solution's peak doesn't include it.
+pub fn profile(solver: &Solver, input: &str) -> Result<Profile> {
+    if !ENABLED {
+        return Err(anyhow!(
+            "memory profiling was compiled out, build with --features profile-mem"
+        ));
+    }
+    let (generated, generator) = measure(|| solver.generate(input));
+    let generated = generated?;
+    let (answer, solution) = measure(|| solver.solve(generated.as_ref()));
+    answer?;
+    Ok(Profile {
+        generator,
+        solution,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        let cases = vec![
+            (0, "0 B"),
+            (1023, "1023 B"),
+            (1024, "1.00 KiB"),
+            (58_163, "56.8 KiB"),
+            (300 * 1024 * 1024, "300 MiB"),
+            (5 * 1024 * 1024 * 1024 * 1024, "5120 GiB"),
+        ];
+        for (bytes, expected) in cases {
+            assert_eq!(format_bytes(bytes), expected);
+        }
+
+        let usage = Usage {
+            allocations: 12,
+            bytes: 4096,
+            peak: 2048,
+        };
+        assert_eq!(usage.to_string(), "12 allocs, 4.00 KiB (peak 2.00 KiB)");
+    }
+
+    // Only meaningful with the counting allocator installed
+    #[cfg(feature = "profile-mem")]
+    #[test]
+    fn test_measure() {
+        let (_, usage) = measure(|| {
+            let first: Vec<u8> = Vec::with_capacity(1000);
+            drop(first);
+            let second: Vec<u8> = Vec::with_capacity(600);
+            std::hint::black_box(second)
+        });
+        // Other tests allocate on their own threads meanwhile, so these are lower bounds
+        assert!(usage.allocations >= 2, "{:?}", usage);
+        assert!(usage.bytes >= 1600, "{:?}", usage);
+        assert!(usage.peak >= 1000, "{:?}", usage);
+    }
+}
@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// A problem with a puzzle input, pointing at the text that could not be parsed
+// and describing what was expected there instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    // Line and column both count from 1, the column in characters rather than bytes
+    pub line: usize,
+    pub column: usize,
+    // The offending text, empty when something is missing
+    pub text: String,
+    pub hint: String,
+    // The whole line the text is on, for the diagnostic
+    pub source: String,
+}
+
+impl ParseError {
+    // Point at `token`, which must be a slice of `input`, such as one of its lines or
+    // a word from one. Parsers that only see one line can pass the line as the input,
+    // and have the caller fix up the line number with on_line.
+    pub fn at(day: u32, input: &str, token: &str, hint: impl Into<String>) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + token.len() <= input.len())
+            .expect("ParseError::at should be given a token from the input");
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+        ParseError {
+            day,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: token.to_string(),
+            hint: hint.into(),
+            source: input[line_start..line_end].to_string(),
+        }
+    }
+
+    // Move an error found by a parser that was only given one line to that line of the input
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+
+    // The error with the line it is on and a caret under the offending text:
+    //
+    //     day 4: line 2, column 5: expected a section range like 2-4, found "2x4"
+    //       |
+    //     2 | 1-3,2x4
+    //       |     ^^^
+    pub fn diagnostic(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Only underline the first line of text that spans several
+        let width = self
+            .text
+            .lines()
+            .next()
+            .map_or(0, |line| line.chars().count())
+            .max(1);
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            number,
+            self.source,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}: line {}, column {}: {}",
+            self.day, self.line, self.column, self.hint
+        )?;
+        match self.text.lines().next() {
+            Some(text) if !text.is_empty() => write!(f, ", found {:?}", text),
+            _ => write!(f, ", found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Parse a token of the input as a number, or anything else implementing FromStr
+pub fn number<T: FromStr>(day: u32, input: &str, token: &str, hint: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(day, input, token, hint))
+}
+
+// The empty slice just past the end of `text`, for pointing at something that is missing
+pub fn end(text: &str) -> &str {
+    &text[text.len()..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        struct TestCase {
+            description: &'static str,
+            input: &'static str,
+            start: usize,
+            len: usize,
+            expected: (usize, usize, &'static str, &'static str),
+        }
+        let cases = vec![
+            TestCase {
+                description: "first line",
+                input: "1-3,2x4\n5-6,7-8",
+                start: 4,
+                len: 3,
+                expected: (1, 5, "2x4", "1-3,2x4"),
+            },
+            TestCase {
+                description: "last line",
+                input: "1-3,2-4\n5-6,7x8",
+                start: 12,
+                len: 3,
+                expected: (2, 5, "7x8", "5-6,7x8"),
+            },
+            TestCase {
+                description: "whole line",
+                input: "100\n\nabc\n200",
+                start: 5,
+                len: 3,
+                expected: (3, 1, "abc", "abc"),
+            },
+            TestCase {
+                description: "missing at the end",
+                input: "[1]\n[2",
+                start: 6,
+                len: 0,
+                expected: (2, 3, "", "[2"),
+            },
+            TestCase {
+                description: "columns count characters",
+                input: "µs x",
+                start: 4,
+                len: 1,
+                expected: (1, 4, "x", "µs x"),
+            },
+        ];
+        for case in cases {
+            let token = &case.input[case.start..case.start + case.len];
+            let got = ParseError::at(2, case.input, token, "hint");
+            assert_eq!(
+                (got.line, got.column, got.text.as_str(), got.source.as_str()),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let input = "1-3,2-4\n5-6,7x8";
+        let error = ParseError::at(
+            4,
+            input,
+            &input[12..15],
+            "expected a section range like 2-4",
+        );
+        assert_eq!(
+            error.diagnostic(),
+            "\
+day 4: line 2, column 5: expected a section range like 2-4, found \"7x8\"
+  |
+2 | 5-6,7x8
+  |     ^^^"
+        );
+
+        let line = "[1,2";
+        let error = ParseError::at(13, line, end(line), "expected ]").on_line(10);
+        assert_eq!(
+            error.diagnostic(),
+            "\
+day 13: line 10, column 5: expected ], found nothing
+   |
+10 | [1,2
+   |     ^"
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        let input = "12 x";
+        assert_eq!(
+            number::<u32>(1, input, &input[..2], "expected a number"),
+            Ok(12)
+        );
+        let error = number::<u32>(1, input, &input[3..], "expected a number").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+}
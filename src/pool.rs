@@ -0,0 +1,106 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// One worker per core the process may use, or a single one if that can't be found out
+pub fn workers() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// Apply `f` to every item on up to `workers` threads, returning the results in the order of
+// the items. Each worker takes the next unclaimed item as soon as it is free, so one slow
+// item doesn't hold up the items queued behind it.
+pub fn map<T, R>(items: &[T], workers: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.clamp(1, items.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            return results;
+                        };
+                        results.push((index, f(item)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(results) => results,
+                Err(payload) => std::panic::resume_unwind(payload),
+            })
+            .collect()
+    });
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map() {
+        struct TestCase {
+            description: &'static str,
+            items: Vec<u64>,
+            workers: usize,
+        }
+        let cases = vec![
+            TestCase {
+                description: "no items",
+                items: vec![],
+                workers: 4,
+            },
+            TestCase {
+                description: "single worker",
+                items: vec![3, 1, 2],
+                workers: 1,
+            },
+            TestCase {
+                description: "more workers than items",
+                items: vec![5, 4],
+                workers: 8,
+            },
+            TestCase {
+                description: "no workers still runs on one",
+                items: vec![7],
+                workers: 0,
+            },
+            TestCase {
+                description: "slow items finish last but keep their place",
+                items: (0..20).rev().collect(),
+                workers: 4,
+            },
+        ];
+        for case in cases {
+            let got = map(&case.items, case.workers, |&n| {
+                thread::sleep(Duration::from_millis(n));
+                n * 2
+            });
+            let expected: Vec<u64> = case.items.iter().map(|n| n * 2).collect();
+            assert_eq!(got, expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_map_uses_workers() {
+        let threads = Mutex::new(HashSet::new());
+        map(&[10; 8], 4, |&n| {
+            threads.lock().unwrap().insert(thread::current().id());
+            thread::sleep(Duration::from_millis(n));
+        });
+        assert!(threads.into_inner().unwrap().len() > 1);
+    }
+}
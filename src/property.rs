@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::crosscheck::{self, Outcome, CASES_PER_SIZE, MAX_SIZE};
+use crate::gen::{self, Generator, Rng};
+use crate::runner::Solver;
+use crate::solution::Answer;
+
+// Works out the answer to a part the slow and obvious way, so the real solution can be
+// checked against it. None means the input isn't one the oracle understands, which
+// happens when shrinking cuts an input down into something the puzzle never asks.
+pub type Oracle = fn(&str) -> Option<Answer>;
+
+#[derive(Debug)]
+pub struct Counterexample {
+    pub solver: String,
+    // The seed and size of the generated input the mismatch was first found on
+    pub source: String,
+    // The smallest input found that the solver still gets wrong
+    pub input: String,
+    pub expected: Answer,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outcome = match &self.outcome {
+            Ok(answer) => answer.to_string(),
+            Err(err) => err.clone(),
+        };
+        write!(
+            f,
+            "{} disagrees with the oracle on {}, shrunk to:\n{}\nexpected {}, got {}",
+            self.solver, self.source, self.input, self.expected, outcome
+        )
+    }
+}
+
+// Solve generated inputs of increasing size with `solver` and compare every answer with
+// the oracle's. Returns how many inputs were compared, or the first mismatch shrunk to
+// the smallest input that still shows it.
+pub fn check(
+    solver: &Solver,
+    oracle: Oracle,
+    generator: Generator,
+    seed: u64,
+) -> Result<usize, Box<Counterexample>> {
+    let mut compared = 0;
+    for size in 1..=MAX_SIZE {
+        for case in 0..CASES_PER_SIZE {
+            let seed = seed.wrapping_add(case);
+            let input = generator(&mut Rng::new(seed), size);
+            let Some(expected) = oracle(&input) else {
+                continue;
+            };
+            let outcome = solve(solver, &input);
+            if outcome.as_ref() == Ok(&expected) {
+                compared += 1;
+                continue;
+            }
+
+            let input = shrink(solver, oracle, input, outcome.is_err());
+            return Err(Box::new(Counterexample {
+                solver: solver.name(),
+                source: format!("generated input (seed {}, size {})", seed, size),
+                expected: oracle(&input).expect("shrinking keeps inputs the oracle understands"),
+                outcome: solve(solver, &input),
+                input,
+            }));
+        }
+    }
+    Ok(compared)
+}
+
+// Check every implementation of a registered part against the oracle, on inputs from the
+// day's generator, panicking with the smallest counterexample
+pub fn assert_oracle(year: u32, day: u32, part: u32, oracle: Oracle) {
+    let generator = gen::generator(year, day).expect("every registered day has a generator");
+    let solvers: Vec<Solver> = crate::solvers()
+        .into_iter()
+        .filter(|solver| (solver.year, solver.day, solver.part) == (year, day, part))
+        .collect();
+    assert!(
+        !solvers.is_empty(),
+        "{} day {} part {} is not registered",
+        year,
+        day,
+        part
+    );
+    for solver in &solvers {
+        match check(solver, oracle, generator, 0) {
+            Ok(compared) => assert!(compared > 0, "{} was never compared", solver.name()),
+            Err(counterexample) => panic!("{}", counterexample),
+        }
+    }
+}
+
+fn solve(solver: &Solver, input: &str) -> Outcome {
+    crosscheck::outcomes(&[solver], input).remove(0)
+}
+
+// Greedily take the first smaller candidate the solver still gets wrong, in the same way:
+// a wrong answer stays a wrong answer, and an error or panic stays one
+fn shrink(solver: &Solver, oracle: Oracle, input: String, failed: bool) -> String {
+    let still_wrong = |candidate: &str| match oracle(candidate) {
+        Some(expected) => match solve(solver, candidate) {
+            Ok(answer) => !failed && answer != expected,
+            Err(_) => failed,
+        },
+        None => false,
+    };
+
+    let mut input = input;
+    'shrinking: loop {
+        for candidate in crosscheck::candidates(&input) {
+            if still_wrong(&candidate) {
+                input = candidate;
+                continue 'shrinking;
+            }
+        }
+        return input;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day1() -> Solver {
+        crate::solvers()
+            .into_iter()
+            .find(|solver| (solver.day, solver.part) == (1, 1))
+            .unwrap()
+    }
+
+    // The largest block of calories, the real way and wrongly as the first block
+    fn largest(input: &str) -> Option<Answer> {
+        let blocks = input.split("\n\n").map(|block| {
+            block
+                .lines()
+                .map(|line| line.parse::<usize>().ok())
+                .sum::<Option<usize>>()
+        });
+        blocks
+            .collect::<Option<Vec<usize>>>()?
+            .into_iter()
+            .max()
+            .map(Answer::from)
+    }
+
+    fn first(input: &str) -> Option<Answer> {
+        let block = input.split("\n\n").next()?;
+        let calories = block.lines().map(|line| line.parse::<usize>().ok());
+        calories.sum::<Option<usize>>().map(Answer::from)
+    }
+
+    #[test]
+    fn test_check() {
+        let solver = day1();
+        let generator = gen::generator(2022, 1).unwrap();
+
+        let compared = check(&solver, largest, generator, 0).unwrap();
+        assert_eq!(compared, MAX_SIZE * CASES_PER_SIZE as usize);
+
+        let counterexample = check(&solver, first, generator, 0).unwrap_err();
+        assert_eq!(counterexample.solver, "2022 Day 1 - Part 1");
+        // Two elves with one snack each, the second with more calories than the first
+        let elves: Vec<&str> = counterexample.input.split("\n\n").collect();
+        assert_eq!(elves.len(), 2, "{}", counterexample);
+        assert!(
+            elves.iter().all(|elf| !elf.contains('\n')),
+            "{}",
+            counterexample
+        );
+        let (expected, answer) = (first(elves[0]).unwrap(), largest(elves[1]).unwrap());
+        assert_eq!(counterexample.expected, expected);
+        assert_eq!(counterexample.outcome, Ok(answer));
+    }
+}
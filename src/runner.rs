@@ -0,0 +1,225 @@
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::solution::{Answer, Registered};
+
+// A Solver is one runnable part (or variant) of a registered day.
+// Parsing and solving are exposed separately so the runner can time them separately.
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    solution: &'static dyn Registered,
+}
+
+pub struct Run {
+    pub answer: Answer,
+    pub generator: Duration,
+    pub solution: Duration,
+}
+
+// One year's registered days
+pub struct Year {
+    pub year: u32,
+    pub solutions: &'static [&'static dyn Registered],
+}
+
+impl Year {
+    pub const fn new(year: u32, solutions: &'static [&'static dyn Registered]) -> Year {
+        Year { year, solutions }
+    }
+}
+
+// Flatten a registry into one Solver per year, part and variant
+pub fn solvers(years: &'static [Year]) -> Vec<Solver> {
+    years
+        .iter()
+        .flat_map(|year| {
+            year.solutions.iter().flat_map(move |&solution| {
+                solution.parts().into_iter().map(move |part| Solver {
+                    year: year.year,
+                    day: part.day,
+                    part: part.part,
+                    variant: part.variant,
+                    solution,
+                })
+            })
+        })
+        .collect()
+}
+
+impl Solver {
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Any>> {
+        self.solution.parse(input)
+    }
+
+    pub fn solve(&self, input: &dyn Any) -> Result<Answer> {
+        self.solution.solve(input, self.part, self.variant)
+    }
+
+    pub fn run(&self, input: &str) -> Result<Run> {
+        let start = Instant::now();
+        let generated = self.generate(input)?;
+        let generator = start.elapsed();
+
+        let start = Instant::now();
+        let answer = self.solve(generated.as_ref())?;
+        let solution = start.elapsed();
+
+        Ok(Run {
+            answer,
+            generator,
+            solution,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        let name = format!("{} Day {} - Part {}", self.year, self.day, self.part);
+        match self.variant {
+            Some(variant) => format!("{} - {}", name, variant),
+            None => name,
+        }
+    }
+}
+
+// Selection narrows the registered solvers down to the ones requested on the command line.
+// Leaving a field empty selects everything.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub variant: Option<String>,
+}
+
+impl Selection {
+    pub fn matches(&self, solver: &Solver) -> bool {
+        self.matches_part(solver.year, solver.day, solver.part, solver.variant)
+    }
+
+    pub fn matches_part(&self, year: u32, day: u32, part: u32, variant: Option<&str>) -> bool {
+        self.year.is_none_or(|selected| selected == year)
+            && self.day.is_none_or(|selected| selected == day)
+            && self.part.is_none_or(|selected| selected == part)
+            && self
+                .variant
+                .as_ref()
+                .is_none_or(|selected| Some(selected.as_str()) == variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Digits;
+    use crate::parse::ParseError;
+    use crate::solution::{Solution, SolveError};
+
+    struct Shout;
+
+    impl Solution for Shout {
+        const DAY: u32 = 2;
+
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String, ParseError> {
+            Ok(input.to_uppercase())
+        }
+
+        fn part1(input: &String) -> Result<Answer, SolveError> {
+            Ok(input.clone().into())
+        }
+
+        fn part2(input: &String) -> Result<Answer, SolveError> {
+            Ok(input.len().into())
+        }
+    }
+
+    static REGISTRY: &[Year] = &[
+        Year::new(2021, &[&Shout]),
+        Year::new(2022, &[&Digits, &Shout]),
+    ];
+
+    #[test]
+    fn test_run() {
+        let solvers = solvers(REGISTRY);
+        let answers: Vec<String> = solvers
+            .iter()
+            .map(|solver| solver.run("a9b7").unwrap().answer.to_string())
+            .collect();
+        assert_eq!(answers, vec!["A9B7", "4", "16", "9", "A9B7", "4"]);
+    }
+
+    #[test]
+    fn test_selection() {
+        struct TestCase {
+            description: &'static str,
+            selection: Selection,
+            expected: Vec<&'static str>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "everything",
+                selection: Selection::default(),
+                expected: vec![
+                    "2021 Day 2 - Part 1",
+                    "2021 Day 2 - Part 2",
+                    "2022 Day 1 - Part 1",
+                    "2022 Day 1 - Part 1 - buggy",
+                    "2022 Day 2 - Part 1",
+                    "2022 Day 2 - Part 2",
+                ],
+            },
+            TestCase {
+                description: "one year",
+                selection: Selection {
+                    year: Some(2021),
+                    ..Selection::default()
+                },
+                expected: vec!["2021 Day 2 - Part 1", "2021 Day 2 - Part 2"],
+            },
+            TestCase {
+                description: "one day",
+                selection: Selection {
+                    day: Some(1),
+                    ..Selection::default()
+                },
+                expected: vec!["2022 Day 1 - Part 1", "2022 Day 1 - Part 1 - buggy"],
+            },
+            TestCase {
+                description: "one part of one year",
+                selection: Selection {
+                    year: Some(2022),
+                    part: Some(1),
+                    ..Selection::default()
+                },
+                expected: vec![
+                    "2022 Day 1 - Part 1",
+                    "2022 Day 1 - Part 1 - buggy",
+                    "2022 Day 2 - Part 1",
+                ],
+            },
+            TestCase {
+                description: "named variant",
+                selection: Selection {
+                    variant: Some("buggy".to_string()),
+                    ..Selection::default()
+                },
+                expected: vec!["2022 Day 1 - Part 1 - buggy"],
+            },
+        ];
+
+        let solvers = solvers(REGISTRY);
+        for case in cases {
+            let got: Vec<String> = solvers
+                .iter()
+                .filter(|solver| case.selection.matches(solver))
+                .map(|solver| solver.name())
+                .collect();
+            assert_eq!(got, case.expected, "{}", case.description);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::answers::Status;
+use crate::solution::Answer;
+
+pub const SAMPLES_DIR: &str = "samples";
+
+// Separates the expected answers from the puzzle input in a sample file
+const SEPARATOR: &str = "---";
+
+// A sample input from a puzzle description, stored as samples/<year>/dayN.txt or
+// samples/<year>/dayN-name.txt so a day can have several. The file starts with the
+// expected answers, then a separator line, then the input:
+//
+//     part1 = 24000
+//     part2 = 45000
+//     ---
+//     1000
+//     2000
+//
+// Files without a separator are treated as input with no expected answers.
+#[derive(Debug)]
+pub struct Sample {
+    pub path: PathBuf,
+    pub year: u32,
+    pub day: u32,
+    pub input: String,
+    pub expected: HashMap<u32, Answer>,
+}
+
+impl Sample {
+    pub fn load(path: &Path) -> Result<Sample> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("could not read sample {}", path.display()))?;
+        Sample::parse(path, &contents).with_context(|| format!("invalid sample {}", path.display()))
+    }
+
+    pub fn parse(path: &Path, contents: &str) -> Result<Sample> {
+        let day = sample_day(path)
+            .ok_or_else(|| anyhow!("sample files should be named dayN.txt or dayN-name.txt"))?;
+        let year = sample_year(path)
+            .ok_or_else(|| anyhow!("sample files should be in a directory named after the year"))?;
+
+        let (header, input) = match contents.split_once(&format!("\n{}\n", SEPARATOR)) {
+            Some((header, input)) => (header, input),
+            None => ("", contents),
+        };
+
+        let mut expected = HashMap::new();
+        let table: toml::Table = header.parse()?;
+        for (key, value) in table.iter() {
+            let part = key
+                .strip_prefix("part")
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or_else(|| anyhow!("invalid key {}, expected partN", key))?;
+            let answer = match value {
+                toml::Value::Integer(n) => Answer::Number(*n),
+                toml::Value::String(s) => Answer::Text(s.clone()),
+                _ => return Err(anyhow!("{} should be a number or a string", key)),
+            };
+            expected.insert(part, answer);
+        }
+
+        Ok(Sample {
+            path: path.to_path_buf(),
+            year,
+            day,
+            // Strip the trailing newline like real inputs
+            input: input.trim_end_matches('\n').to_string(),
+            expected,
+        })
+    }
+
+    pub fn check(&self, part: u32, answer: &Answer) -> Status {
+        match self.expected.get(&part) {
+            Some(expected) if expected.to_string() == answer.to_string() => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+            },
+            None => Status::Missing,
+        }
+    }
+}
+
+// Find every sample, optionally only for one year and day, sorted by year, day then name
+pub fn find(dir: &Path, year: Option<u32>, day: Option<u32>) -> Result<Vec<Sample>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths: Vec<(u32, u32, PathBuf)> = vec![];
+    for year_dir in read_dir(dir)? {
+        if sample_year_dir(&year_dir).is_none_or(|found| year.is_some_and(|year| year != found)) {
+            continue;
+        }
+        paths.extend(
+            read_dir(&year_dir)?
+                .into_iter()
+                .filter_map(|path| Some((sample_year(&path)?, sample_day(&path)?, path)))
+                .filter(|(_, sample_day, _)| day.is_none_or(|day| day == *sample_day)),
+        );
+    }
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|(_, _, path)| Sample::load(path))
+        .collect()
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    Ok(fs::read_dir(dir)
+        .with_context(|| format!("could not read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect())
+}
+
+// Read the year out of the directory a sample is in, like samples/2022/day6.txt
+fn sample_year(path: &Path) -> Option<u32> {
+    sample_year_dir(path.parent()?)
+}
+
+fn sample_year_dir(dir: &Path) -> Option<u32> {
+    dir.file_name()?.to_str()?.parse().ok()
+}
+
+// Read the day number out of a name like day6.txt or day6-easy.txt
+pub(crate) fn sample_day(path: &Path) -> Option<u32> {
+    if path.extension()? != "txt" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let day = stem.strip_prefix("day")?;
+    let day = day.split_once('-').map_or(day, |(day, _)| day);
+    day.parse::<u32>().ok()
+}
+
+// Run every registered solver for a sample's day and part, and assert it gets the expected answer.
+// build.rs generates one test calling this for every sample file and part.
+#[cfg(test)]
+pub fn assert_sample(path: &str, part: u32) {
+    let sample = Sample::load(Path::new(path)).unwrap();
+    let expected = &sample.expected[&part];
+
+    let solvers: Vec<_> = crate::solvers()
+        .into_iter()
+        .filter(|solver| (solver.year, solver.day, solver.part) == (sample.year, sample.day, part))
+        .collect();
+    assert!(
+        !solvers.is_empty(),
+        "{} has an answer for {} day {} part {}, which is not registered",
+        path,
+        sample.year,
+        sample.day,
+        part
+    );
+
+    for solver in solvers {
+        let run = solver
+            .run(&sample.input)
+            .unwrap_or_else(|err| panic!("{} failed on {}: {:#}", solver.name(), path, err));
+        assert_eq!(
+            run.answer.to_string(),
+            expected.to_string(),
+            "{} on {}",
+            solver.name(),
+            path
+        );
+    }
+}
+
+#[cfg(test)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let sample = Sample::parse(
+            Path::new("samples/2022/day5-small.txt"),
+            "part1 = \"CMZ\"\npart2 = 12\n---\n    [D]    \n[N] [C]    \n\n",
+        )
+        .unwrap();
+        assert_eq!((sample.year, sample.day), (2022, 5));
+        assert_eq!(sample.input, "    [D]    \n[N] [C]    ");
+        assert_eq!(sample.expected[&1], Answer::Text("CMZ".to_string()));
+        assert_eq!(sample.expected[&2], Answer::Number(12));
+
+        let sample = Sample::parse(
+            Path::new("samples/2021/day6.txt"),
+            "bvwbjplbgvbhsrlpgdmjqwftvncz\n",
+        )
+        .unwrap();
+        assert_eq!((sample.year, sample.day), (2021, 6));
+        assert_eq!(sample.input, "bvwbjplbgvbhsrlpgdmjqwftvncz");
+        assert!(sample.expected.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let cases = vec![
+            ("bad name", "samples/2022/six.txt", "part1 = 5\n---\nabc"),
+            ("no year", "samples/day6.txt", "part1 = 5\n---\nabc"),
+            ("bad key", "samples/2022/day6.txt", "first = 5\n---\nabc"),
+            (
+                "bad answer",
+                "samples/2022/day6.txt",
+                "part1 = [5]\n---\nabc",
+            ),
+        ];
+        for (description, path, contents) in cases {
+            assert!(
+                Sample::parse(Path::new(path), contents).is_err(),
+                "{} did not get expected error",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_day() {
+        let cases = vec![
+            ("samples/2022/day6.txt", Some(6)),
+            ("samples/2022/day12-small.txt", Some(12)),
+            ("samples/2022/day12.md", None),
+            ("samples/2022/notes.txt", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(sample_day(Path::new(path)), expected, "{}", path);
+        }
+    }
+}
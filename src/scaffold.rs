@@ -0,0 +1,639 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::samples::SAMPLES_DIR;
+
+// The module a new day starts from, with {{DAY}} standing in for the day number
+const TEMPLATE: &str = include_str!("../templates/day.rs.template");
+
+// The registry of a new year's days, with {{YEAR}} standing in for the year
+const YEAR_TEMPLATE: &str = include_str!("../templates/year.rs.template");
+
+// The fuzz target of a new day's parser, with {{YEAR}} and {{DAY}} standing in as above
+const FUZZ_TARGET_TEMPLATE: &str = include_str!("../templates/fuzz_target.rs.template");
+
+// The fuzz crate's entry for one target, named {{NAME}}
+const FUZZ_BIN: &str = "\
+[[bin]]
+name = \"{{NAME}}\"
+path = \"fuzz_targets/{{NAME}}.rs\"
+test = false
+doc = false
+bench = false
+";
+
+// Comments are ignored by the sample header, so the stub has no expected answers
+// (and generates no tests) until they are filled in
+const SAMPLE_STUB: &str = "\
+# Paste the example input below the --- line, and its answers here, like
+# part1 = 24000
+---
+";
+
+const LIB: &str = "src/lib.rs";
+
+// The fuzz crate, which lists every target as a binary of its own
+const FUZZ_MANIFEST: &str = "fuzz/Cargo.toml";
+
+// The first year of Advent of Code
+const FIRST_YEAR: u32 = 2015;
+
+// What scaffolding did to one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Created(PathBuf),
+    Updated(PathBuf),
+    Unchanged(PathBuf),
+}
+
+impl Change {
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Created(path) | Change::Updated(path) | Change::Unchanged(path) => path,
+        }
+    }
+}
+
+pub fn render(day: u32) -> String {
+    TEMPLATE.replace("{{DAY}}", &day.to_string())
+}
+
+// Create the module, sample stub, fuzz target and registry entries for a day under `root`,
+// skipping whatever already exists. A year seen for the first time gets its own module,
+// registered in lib.rs. Running it again changes nothing, and a module that already holds
+// anything but the untouched template is never overwritten.
+pub fn scaffold(root: &Path, year: u32, day: u32) -> Result<Vec<Change>> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("day should be from 1 to 25, not {}", day));
+    }
+    if year < FIRST_YEAR {
+        return Err(anyhow!(
+            "year should be {} or later, not {}",
+            FIRST_YEAR,
+            year
+        ));
+    }
+
+    let year_dir = PathBuf::from(format!("src/year{}", year));
+    let module = year_dir.join(format!("day{}.rs", day));
+    let existing = read_optional(&root.join(&module))?;
+    if existing
+        .as_deref()
+        .is_some_and(|existing| !existing.is_empty() && !is_template(existing, day))
+    {
+        return Err(anyhow!(
+            "{} already has a solution, refusing to overwrite it",
+            module.display()
+        ));
+    }
+
+    let sample = Path::new(SAMPLES_DIR)
+        .join(year.to_string())
+        .join(format!("day{}.txt", day));
+    let lib = read_optional(&root.join(LIB))?
+        .ok_or_else(|| anyhow!("could not find {} under {}", LIB, root.display()))?;
+    let fuzz_target = PathBuf::from(format!("fuzz/fuzz_targets/year{}_day{}.rs", year, day));
+    let fuzz_manifest = read_optional(&root.join(FUZZ_MANIFEST))?
+        .ok_or_else(|| anyhow!("could not find {} under {}", FUZZ_MANIFEST, root.display()))?;
+    let registry = year_dir.join("mod.rs");
+    let days = read_optional(&root.join(&registry))?;
+
+    let lib_entry = format!(
+        "    Year::new(year{}::YEAR, year{}::SOLUTIONS),",
+        year, year
+    );
+    let registered_lib = register(&lib, "year", year, "pub static YEARS", lib_entry)
+        .with_context(|| format!("could not register year {} in {}", year, LIB))?;
+    let day_entry = format!("    &day{}::Day{},", day, day);
+    let template = YEAR_TEMPLATE.replace("{{YEAR}}", &year.to_string());
+    let registered_days = register(
+        days.as_deref().unwrap_or(&template),
+        "day",
+        day,
+        "pub static SOLUTIONS",
+        day_entry,
+    )
+    .with_context(|| format!("could not register day {} in {}", day, registry.display()))?;
+
+    let mut changes = vec![
+        create(root, module, &render(day))?,
+        create(root, sample, SAMPLE_STUB)?,
+        create(root, fuzz_target, &render_fuzz_target(year, day))?,
+    ];
+    changes.push(update(root, registry, days.as_deref(), &registered_days)?);
+    changes.push(update(
+        root,
+        PathBuf::from(LIB),
+        Some(&lib),
+        &registered_lib,
+    )?);
+    changes.push(update(
+        root,
+        PathBuf::from(FUZZ_MANIFEST),
+        Some(&fuzz_manifest),
+        &add_fuzz_target(&fuzz_manifest, year, day),
+    )?);
+    Ok(changes)
+}
+
+// Whether a day's module is still the template it was scaffolded from, with no solution yet
+pub fn is_template(source: &str, day: u32) -> bool {
+    source == render(day)
+}
+
+fn render_fuzz_target(year: u32, day: u32) -> String {
+    FUZZ_TARGET_TEMPLATE
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+}
+
+// Add a day's fuzz target to the end of the fuzz crate's binaries, unless it's listed already
+fn add_fuzz_target(manifest: &str, year: u32, day: u32) -> String {
+    let name = format!("year{}_day{}", year, day);
+    if manifest.contains(&format!("name = \"{}\"\n", name)) {
+        return manifest.to_string();
+    }
+    let bin = FUZZ_BIN.replace("{{NAME}}", &name);
+    format!("{}\n\n{}", manifest.trim_end(), bin)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match path.exists() {
+        true => fs::read_to_string(path)
+            .map(Some)
+            .with_context(|| format!("could not read {}", path.display())),
+        false => Ok(None),
+    }
+}
+
+// Write `contents` to `path` if it is missing or empty, leaving anything else alone
+fn create(root: &Path, path: PathBuf, contents: &str) -> Result<Change> {
+    let full = root.join(&path);
+    let change = match read_optional(&full)?.as_deref() {
+        None => Change::Created(path),
+        Some("") => Change::Updated(path),
+        Some(_) => return Ok(Change::Unchanged(path)),
+    };
+    if let Some(parent) = full.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create {}", parent.display()))?;
+    }
+    fs::write(&full, contents).with_context(|| format!("could not write {}", full.display()))?;
+    Ok(change)
+}
+
+// Replace the `existing` contents of `path` with `contents`, if they differ
+fn update(root: &Path, path: PathBuf, existing: Option<&str>, contents: &str) -> Result<Change> {
+    let change = match existing {
+        Some(existing) if existing == contents => return Ok(Change::Unchanged(path)),
+        Some(_) => Change::Updated(path),
+        None => Change::Created(path),
+    };
+    let full = root.join(change.path());
+    if let Some(parent) = full.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create {}", parent.display()))?;
+    }
+    fs::write(&full, contents).with_context(|| format!("could not write {}", full.display()))?;
+    Ok(change)
+}
+
+// Add `pub mod <prefix>N;` and `entry` to the static `list` in a registry. Modules are kept
+// in the order rustfmt sorts them, by name, and entries in the order of their numbers.
+// lib.rs lists years this way, and each year's module lists its days.
+pub fn register(
+    source: &str,
+    prefix: &str,
+    number: u32,
+    list: &str,
+    entry: String,
+) -> Result<String> {
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+
+    let module = format!("pub mod {}{};", prefix, number);
+    if !lines.iter().any(|line| line.trim() == module) {
+        let modules = 0..lines.len();
+        let name = format!("{}{}", prefix, number);
+        let module_name = |line: &str| module_name(line, prefix);
+        if insert_in_order(&mut lines, modules, name, module_name, module.clone()).is_none() {
+            // The first module goes above the registry's items
+            let first = lines
+                .iter()
+                .position(|line| line.starts_with("pub const") || line.starts_with("pub static"))
+                .ok_or_else(|| anyhow!("could not find where to add {}", module))?;
+            lines.splice(first..first, [module, String::new()]);
+        }
+    }
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with(list))
+        .ok_or_else(|| anyhow!("could not find {}", list))?;
+    // Only a list that gets a new entry is spread out
+    let mut spread = lines.clone();
+    spread_list(&mut spread, start);
+    if !spread.iter().any(|line| line.trim() == entry.trim()) {
+        lines = spread;
+        let end = lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .map(|end| start + end)
+            .ok_or_else(|| anyhow!("could not find the end of {}", list))?;
+        let entry_number = |line: &str| entry_number(line, prefix);
+        if insert_in_order(
+            &mut lines,
+            start + 1..end,
+            number,
+            entry_number,
+            entry.clone(),
+        )
+        .is_none()
+        {
+            lines.insert(end, entry);
+        }
+        join_list(&mut lines, start);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// How wide rustfmt lets a list's entries and the whole line get before it puts each entry
+// on a line of its own
+const LIST_WIDTH: usize = 60;
+const MAX_WIDTH: usize = 100;
+
+// rustfmt puts a list short enough to fit on the line it starts on, like a single year, so
+// spread it back out to one entry per line
+fn spread_list(lines: &mut Vec<String>, start: usize) {
+    let Some((head, items)) = lines[start].split_once("= &[") else {
+        return;
+    };
+    let Some(items) = items.strip_suffix("];") else {
+        return;
+    };
+
+    let mut spread = vec![format!("{}= &[", head)];
+    let mut depth = 0;
+    let mut item = String::new();
+    for c in items.chars().chain([',']) {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                if !item.trim().is_empty() {
+                    spread.push(format!("    {},", item.trim()));
+                }
+                item.clear();
+                continue;
+            }
+            _ => {}
+        }
+        item.push(c);
+    }
+    spread.push("];".to_string());
+    lines.splice(start..=start, spread);
+}
+
+// Put a spread out list back on the line it starts on if it's short enough, as rustfmt does
+fn join_list(lines: &mut Vec<String>, start: usize) {
+    let Some(end) = lines[start..].iter().position(|line| line == "];") else {
+        return;
+    };
+    let entries: Vec<&str> = lines[start + 1..start + end]
+        .iter()
+        .map(|line| line.trim().trim_end_matches(','))
+        .collect();
+    let entries = entries.join(", ");
+    let joined = format!("{}{}];", lines[start], entries);
+    if entries.len() <= LIST_WIDTH && joined.len() <= MAX_WIDTH {
+        lines.splice(start..=start + end, [joined]);
+    }
+}
+
+// Insert `new` among the lines in `range` that `key_of` recognises, before the first one
+// with a later key. None if no line in the range is recognised.
+fn insert_in_order<K: Ord>(
+    lines: &mut Vec<String>,
+    range: std::ops::Range<usize>,
+    key: K,
+    key_of: impl Fn(&str) -> Option<K>,
+    new: String,
+) -> Option<()> {
+    let keys: Vec<(usize, K)> = range
+        .filter_map(|index| key_of(&lines[index]).map(|key| (index, key)))
+        .collect();
+    let index = match keys.iter().find(|(_, other)| *other > key) {
+        Some((index, _)) => *index,
+        None => keys.last()?.0 + 1,
+    };
+    lines.insert(index, new);
+    Some(())
+}
+
+// The name of a numbered module in a line like `pub mod day12;`
+fn module_name(line: &str, prefix: &str) -> Option<String> {
+    let name = line.trim().strip_prefix("pub mod ")?.strip_suffix(';')?;
+    name.strip_prefix(prefix)?.parse::<u32>().ok()?;
+    Some(name.to_string())
+}
+
+// The number of the module an entry refers to, like `&day12::Day12,` or
+// `Year::new(year2022::YEAR, year2022::SOLUTIONS),`
+fn entry_number(line: &str, prefix: &str) -> Option<u32> {
+    line.split_once(prefix)?.1.split_once("::")?.0.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = "\
+pub mod runner;
+
+pub mod year2022;
+
+pub static YEARS: &[Year] = &[Year::new(year2022::YEAR, year2022::SOLUTIONS)];
+";
+
+    const YEAR_RS: &str = "\
+use crate::solution::Registered;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day5;
+
+pub const YEAR: u32 = 2022;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day5::Day5,
+    &day10::Day10,
+];
+";
+
+    const FUZZ_CARGO_TOML: &str = "\
+[package]
+name = \"advent-of-code-fuzz\"
+
+[[bin]]
+name = \"year2022_day1\"
+path = \"fuzz_targets/year2022_day1.rs\"
+test = false
+doc = false
+bench = false
+
+";
+
+    #[test]
+    fn test_register() {
+        struct TestCase {
+            description: &'static str,
+            source: &'static str,
+            day: u32,
+            expected: &'static str,
+        }
+        let cases = vec![
+            TestCase {
+                description: "between existing days",
+                source: YEAR_RS,
+                day: 4,
+                expected: "\
+use crate::solution::Registered;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+
+pub const YEAR: u32 = 2022;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day10::Day10,
+];
+",
+            },
+            TestCase {
+                description: "modules by name like rustfmt sorts them, entries by number",
+                source: YEAR_RS,
+                day: 12,
+                expected: "\
+use crate::solution::Registered;
+
+pub mod day1;
+pub mod day10;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day5;
+
+pub const YEAR: u32 = 2022;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day5::Day5,
+    &day10::Day10,
+    &day12::Day12,
+];
+",
+            },
+            TestCase {
+                description: "already registered",
+                source: YEAR_RS,
+                day: 10,
+                expected: YEAR_RS,
+            },
+            TestCase {
+                description: "short list kept on one line",
+                source: "\
+pub mod day1;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[&day1::Day1];
+",
+                day: 2,
+                expected: "\
+pub mod day1;
+pub mod day2;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[&day1::Day1, &day2::Day2];
+",
+            },
+            TestCase {
+                description: "list grown too long for one line",
+                source: "\
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[&day1::Day1, &day2::Day2, &day3::Day3, &day4::Day4];
+",
+                day: 5,
+                expected: "\
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+
+pub static SOLUTIONS: &[&dyn Registered] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+];
+",
+            },
+            TestCase {
+                description: "first day of a year",
+                source: YEAR_TEMPLATE,
+                day: 3,
+                expected: "\
+use crate::solution::Registered;
+
+pub mod day3;
+
+pub const YEAR: u32 = {{YEAR}};
+
+// Every solved day, in order. Days are added here once they implement `Solution`
+pub static SOLUTIONS: &[&dyn Registered] = &[&day3::Day3];
+",
+            },
+        ];
+        for case in cases {
+            let entry = format!("    &day{}::Day{},", case.day, case.day);
+            let register = |source: &str| {
+                register(
+                    source,
+                    "day",
+                    case.day,
+                    "pub static SOLUTIONS",
+                    entry.clone(),
+                )
+            };
+            let registered = register(case.source).unwrap();
+            assert_eq!(registered, case.expected, "{}", case.description);
+            assert_eq!(
+                register(&registered).unwrap(),
+                registered,
+                "{}",
+                case.description
+            );
+        }
+
+        assert!(register(
+            "fn main() {}\n",
+            "day",
+            3,
+            "pub static SOLUTIONS",
+            String::new()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/year2022")).unwrap();
+        fs::write(root.join(LIB), LIB_RS).unwrap();
+        fs::write(root.join("src/year2022/mod.rs"), YEAR_RS).unwrap();
+        fs::create_dir_all(root.join("fuzz")).unwrap();
+        fs::write(root.join(FUZZ_MANIFEST), FUZZ_CARGO_TOML).unwrap();
+        let module = PathBuf::from("src/year2022/day7.rs");
+        let sample = PathBuf::from("samples/2022/day7.txt");
+        let fuzz_target = PathBuf::from("fuzz/fuzz_targets/year2022_day7.rs");
+        let registry = PathBuf::from("src/year2022/mod.rs");
+
+        assert_eq!(
+            scaffold(&root, 2022, 7).unwrap(),
+            vec![
+                Change::Created(module.clone()),
+                Change::Created(sample.clone()),
+                Change::Created(fuzz_target.clone()),
+                Change::Updated(registry.clone()),
+                Change::Unchanged(PathBuf::from(LIB)),
+                Change::Updated(PathBuf::from(FUZZ_MANIFEST)),
+            ]
+        );
+        let contents = fs::read_to_string(root.join(&module)).unwrap();
+        assert!(contents.contains("impl Solution for Day7 {"));
+        assert!(!contents.contains("{{DAY}}"));
+        assert!(is_template(&contents, 7));
+        let target = fs::read_to_string(root.join(&fuzz_target)).unwrap();
+        assert!(target.contains("advent_of_code::fuzz::parse(2022, 7, data)"));
+        let manifest = fs::read_to_string(root.join(FUZZ_MANIFEST)).unwrap();
+        assert_eq!(
+            manifest,
+            FUZZ_CARGO_TOML.to_string() + &FUZZ_BIN.replace("{{NAME}}", "year2022_day7")
+        );
+
+        // Running it again changes nothing
+        assert_eq!(
+            scaffold(&root, 2022, 7).unwrap(),
+            vec![
+                Change::Unchanged(module.clone()),
+                Change::Unchanged(sample),
+                Change::Unchanged(fuzz_target),
+                Change::Unchanged(registry),
+                Change::Unchanged(PathBuf::from(LIB)),
+                Change::Unchanged(PathBuf::from(FUZZ_MANIFEST)),
+            ]
+        );
+
+        // Once the module has been worked on it is left alone
+        fs::write(root.join(&module), "// solved\n").unwrap();
+        assert!(scaffold(&root, 2022, 7).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join(&module)).unwrap(),
+            "// solved\n"
+        );
+
+        // A new year gets its own registry, listed in lib.rs
+        assert_eq!(
+            scaffold(&root, 2023, 1).unwrap(),
+            vec![
+                Change::Created(PathBuf::from("src/year2023/day1.rs")),
+                Change::Created(PathBuf::from("samples/2023/day1.txt")),
+                Change::Created(PathBuf::from("fuzz/fuzz_targets/year2023_day1.rs")),
+                Change::Created(PathBuf::from("src/year2023/mod.rs")),
+                Change::Updated(PathBuf::from(LIB)),
+                Change::Updated(PathBuf::from(FUZZ_MANIFEST)),
+            ]
+        );
+        let registry = fs::read_to_string(root.join("src/year2023/mod.rs")).unwrap();
+        assert!(registry.contains("pub mod day1;\n"));
+        assert!(registry.contains("pub const YEAR: u32 = 2023;"));
+        assert!(registry.contains(" = &[&day1::Day1];\n"));
+        let lib = fs::read_to_string(root.join(LIB)).unwrap();
+        assert!(lib.contains("pub mod year2022;\npub mod year2023;\n"));
+        assert!(lib.contains("    Year::new(year2023::YEAR, year2023::SOLUTIONS),\n];"));
+
+        assert!(scaffold(&root, 2022, 26).is_err());
+        assert!(scaffold(&root, 2014, 1).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_template_sample_parses() {
+        let path = Path::new("samples/2022/day7.txt");
+        let sample = crate::samples::Sample::parse(path, SAMPLE_STUB).unwrap();
+        assert!(sample.expected.is_empty());
+        assert_eq!(sample.input, "");
+    }
+}
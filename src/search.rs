@@ -0,0 +1,277 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// Anything that can be searched: nodes, and the nodes one step away from each
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    // The nodes reachable in one step from `node`, with what each step costs
+    fn neighbours(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, usize)>;
+}
+
+// A route through a graph, from the start it was found from to the goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    // The total cost of the steps, for bfs just the number of steps
+    pub cost: usize,
+}
+
+impl<N> Path<N> {
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn end(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+}
+
+// Every node seen so far gets an index, so the search only hashes each node when it
+// is first reached and the parent links are plain indices
+struct Visited<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    // The cheapest cost found to each node, and the node it was reached from
+    best: Vec<(usize, Option<usize>)>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new() -> Visited<N> {
+        Visited {
+            nodes: vec![],
+            indices: HashMap::new(),
+            best: vec![],
+        }
+    }
+
+    // Record reaching `node` at `cost`, returning its index if that is cheaper than before
+    fn reach(&mut self, node: N, cost: usize, parent: Option<usize>) -> Option<usize> {
+        match self.indices.get(&node) {
+            Some(&index) if self.best[index].0 <= cost => None,
+            Some(&index) => {
+                self.best[index] = (cost, parent);
+                Some(index)
+            }
+            None => {
+                let index = self.nodes.len();
+                self.indices.insert(node.clone(), index);
+                self.nodes.push(node);
+                self.best.push((cost, parent));
+                Some(index)
+            }
+        }
+    }
+
+    fn path(&self, end: usize) -> Path<N> {
+        let mut nodes = vec![];
+        let mut current = Some(end);
+        while let Some(index) = current {
+            nodes.push(self.nodes[index].clone());
+            current = self.best[index].1;
+        }
+        nodes.reverse();
+        Path {
+            nodes,
+            cost: self.best[end].0,
+        }
+    }
+}
+
+// The path with the fewest steps from any of the starts to a node where `goal` holds,
+// ignoring the cost of each step
+pub fn bfs<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Some(index) = visited.reach(start, 0, None) {
+            queue.push_back(index);
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        let node = visited.nodes[index].clone();
+        if goal(&node) {
+            return Some(visited.path(index));
+        }
+        let steps = visited.best[index].0 + 1;
+        for (neighbour, _) in graph.neighbours(&node) {
+            if visited.indices.contains_key(&neighbour) {
+                continue;
+            }
+            if let Some(neighbour) = visited.reach(neighbour, steps, Some(index)) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    None
+}
+
+// The cheapest path from any of the starts to a node where `goal` holds
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar(graph, starts, goal, |_| 0)
+}
+
+// The cheapest path from any of the starts to a node where `goal` holds, searching
+// towards the goal first. `heuristic` estimates the cost left from a node to the goal,
+// and the path is only guaranteed to be the cheapest if it never overestimates.
+pub fn astar<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut goal: impl FnMut(&G::Node) -> bool,
+    mut heuristic: impl FnMut(&G::Node) -> usize,
+) -> Option<Path<G::Node>> {
+    let mut visited = Visited::new();
+    // Ordered by the estimated total cost, then the cost so far, smallest first
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(index) = visited.reach(start, 0, None) {
+            queue.push(Reverse((estimate, 0, index)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // A cheaper way here was found after this entry was queued
+        if cost > visited.best[index].0 {
+            continue;
+        }
+        let node = visited.nodes[index].clone();
+        if goal(&node) {
+            return Some(visited.path(index));
+        }
+        for (neighbour, step) in graph.neighbours(&node) {
+            let cost = cost + step;
+            let estimate = cost + heuristic(&neighbour);
+            if let Some(neighbour) = visited.reach(neighbour, cost, Some(index)) {
+                queue.push(Reverse((estimate, cost, neighbour)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A directed graph given as edges (from, to, cost)
+    struct Edges(Vec<(u32, u32, usize)>);
+
+    impl Graph for Edges {
+        type Node = u32;
+
+        fn neighbours(&self, node: &u32) -> impl Iterator<Item = (u32, usize)> {
+            self.0
+                .iter()
+                .filter(move |(from, _, _)| from == node)
+                .map(|&(_, to, cost)| (to, cost))
+        }
+    }
+
+    // 1 -> 2 -> 3 -> 4 is cheap, 1 -> 4 is one expensive step, 5 leads to 4 and 6 is
+    // unreachable from everything
+    fn graph() -> Edges {
+        Edges(vec![
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (1, 4, 10),
+            (5, 4, 7),
+            (4, 1, 1),
+        ])
+    }
+
+    #[test]
+    fn test_search() {
+        struct TestCase {
+            description: &'static str,
+            starts: Vec<u32>,
+            goal: u32,
+            // The nodes and cost of the bfs and dijkstra paths
+            expected_bfs: Option<(Vec<u32>, usize)>,
+            expected_dijkstra: Option<(Vec<u32>, usize)>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "bfs takes fewer steps, dijkstra costs less",
+                starts: vec![1],
+                goal: 4,
+                expected_bfs: Some((vec![1, 4], 1)),
+                expected_dijkstra: Some((vec![1, 2, 3, 4], 3)),
+            },
+            TestCase {
+                description: "starting at the goal",
+                starts: vec![3],
+                goal: 3,
+                expected_bfs: Some((vec![3], 0)),
+                expected_dijkstra: Some((vec![3], 0)),
+            },
+            TestCase {
+                description: "the closest of several starts wins",
+                starts: vec![5, 2],
+                goal: 4,
+                expected_bfs: Some((vec![5, 4], 1)),
+                expected_dijkstra: Some((vec![2, 3, 4], 2)),
+            },
+            TestCase {
+                description: "unreachable",
+                starts: vec![1],
+                goal: 6,
+                expected_bfs: None,
+                expected_dijkstra: None,
+            },
+            TestCase {
+                description: "no starts",
+                starts: vec![],
+                goal: 1,
+                expected_bfs: None,
+                expected_dijkstra: None,
+            },
+        ];
+        let graph = graph();
+        for case in cases {
+            let found = |path: Option<Path<u32>>| path.map(|path| (path.nodes, path.cost));
+            let bfs = bfs(&graph, case.starts.clone(), |&node| node == case.goal);
+            let dijkstra = dijkstra(&graph, case.starts.clone(), |&node| node == case.goal);
+            let astar = astar(
+                &graph,
+                case.starts.clone(),
+                |&node| node == case.goal,
+                |_| 1,
+            );
+            assert_eq!(found(bfs), case.expected_bfs, "{}", case.description);
+            assert_eq!(
+                found(dijkstra.clone()),
+                case.expected_dijkstra,
+                "{}",
+                case.description
+            );
+            assert_eq!(
+                astar.map(|path| path.cost),
+                dijkstra.map(|path| path.cost),
+                "{}",
+                case.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_path() {
+        let path = dijkstra(&graph(), [1], |&node| node == 4).unwrap();
+        assert_eq!((path.start(), path.end(), path.steps()), (&1, &4, 3));
+    }
+}
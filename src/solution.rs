@@ -0,0 +1,261 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::parse::ParseError;
+
+// The answer to one part of a puzzle. Most answers are numbers, but some are
+// strings of crate labels or letters drawn on a screen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Numbers too large for an i64 are kept as text rather than wrapped around
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+// Why a part has no answer for an input that parsed. Solutions return these instead of
+// panicking, so the runner can report them and carry on with the other parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    // There is nothing to work with, like a list with no items in it
+    EmptyInput,
+    // The puzzle has no answer for this input, like a maze without a way out
+    NoSolution,
+    // The input breaks something the puzzle promises, like moving a crate off an empty stack
+    InvariantViolated { detail: String },
+    // The part hasn't been solved yet, so it has no answer for any input
+    Unsolved,
+}
+
+impl SolveError {
+    pub fn invariant(detail: impl Into<String>) -> SolveError {
+        SolveError::InvariantViolated {
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::EmptyInput => write!(f, "the input is empty"),
+            SolveError::NoSolution => write!(f, "the input has no solution"),
+            SolveError::InvariantViolated { detail } => {
+                write!(f, "the input breaks an assumption: {}", detail)
+            }
+            SolveError::Unsolved => write!(f, "this part has not been solved yet"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+// An alternative implementation of one part, kept around to compare against the default
+pub struct Variant<I: 'static> {
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<Answer, SolveError>,
+}
+
+// Solution ties a day's parser to its parts. Each day implements it on a unit struct,
+// which is then added to the registry in lib.rs.
+pub trait Solution {
+    const DAY: u32;
+
+    // Parts that have been solved, days that are still in progress can leave out part 2
+    const PARTS: &'static [u32] = &[1, 2];
+
+    const VARIANTS: &'static [Variant<Self::Input>] = &[];
+
+    type Input: 'static;
+
+    // Parse the puzzle input, pointing at the first thing that doesn't match the expected format
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError>;
+}
+
+// One runnable part of a registered day, with the variant name if it is not the default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+}
+
+// Registered is the object safe side of Solution. The parsed input is boxed as `Any`
+// so days with different input types can share one registry.
+pub trait Registered: Sync {
+    fn day(&self) -> u32;
+
+    fn parts(&self) -> Vec<Part>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+
+    fn solve(&self, input: &dyn Any, part: u32, variant: Option<&str>) -> Result<Answer>;
+}
+
+impl<S: Solution + Sync> Registered for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        let mut parts: Vec<Part> = S::PARTS
+            .iter()
+            .map(|&part| Part {
+                day: S::DAY,
+                part,
+                variant: None,
+            })
+            .chain(S::VARIANTS.iter().map(|variant| Part {
+                day: S::DAY,
+                part: variant.part,
+                variant: Some(variant.name),
+            }))
+            .collect();
+        // Keep each part's variants next to its default
+        parts.sort_by_key(|part| (part.part, part.variant.is_some()));
+        parts
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: u32, variant: Option<&str>) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or_else(|| anyhow!("day {} was given input parsed by another day", S::DAY))?;
+
+        match (part, variant) {
+            (1, None) if S::PARTS.contains(&1) => Ok(S::part1(input)?),
+            (2, None) if S::PARTS.contains(&2) => Ok(S::part2(input)?),
+            (part, Some(name)) => {
+                let variant = S::VARIANTS
+                    .iter()
+                    .find(|variant| variant.part == part && variant.name == name)
+                    .ok_or_else(|| {
+                        anyhow!("day {} part {} has no variant {}", S::DAY, part, name)
+                    })?;
+                Ok((variant.solve)(input)?)
+            }
+            (part, None) => Err(anyhow!("day {} has no part {}", S::DAY, part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Digits;
+
+    #[test]
+    fn test_answer_from() {
+        struct TestCase {
+            description: &'static str,
+            answer: Answer,
+            expected: Answer,
+        }
+        let cases = vec![
+            TestCase {
+                description: "zero",
+                answer: 0usize.into(),
+                expected: Answer::Number(0),
+            },
+            TestCase {
+                description: "largest i64",
+                answer: (i64::MAX as usize).into(),
+                expected: Answer::Number(i64::MAX),
+            },
+            TestCase {
+                description: "too large for an i64",
+                answer: usize::MAX.into(),
+                expected: Answer::Text(usize::MAX.to_string()),
+            },
+            TestCase {
+                description: "negative",
+                answer: (-12isize).into(),
+                expected: Answer::Number(-12),
+            },
+        ];
+        for case in cases {
+            assert_eq!(case.answer, case.expected, "{}", case.description);
+        }
+    }
+
+    #[test]
+    fn test_registered() {
+        let registered: &dyn Registered = &Digits;
+        assert_eq!(
+            registered.parts(),
+            vec![
+                Part {
+                    day: 1,
+                    part: 1,
+                    variant: None
+                },
+                Part {
+                    day: 1,
+                    part: 1,
+                    variant: Some("buggy")
+                },
+            ]
+        );
+
+        let input = registered.parse("97").unwrap();
+        assert_eq!(
+            registered.solve(input.as_ref(), 1, None).unwrap(),
+            Answer::Number(16)
+        );
+        assert_eq!(
+            registered.solve(input.as_ref(), 1, Some("buggy")).unwrap(),
+            Answer::Number(9)
+        );
+        assert!(registered.solve(input.as_ref(), 2, None).is_err());
+        assert!(registered
+            .solve(input.as_ref(), 1, Some("tripled"))
+            .is_err());
+        assert!(registered.solve(&0_u8, 1, None).is_err());
+
+        let input = registered.parse("").unwrap();
+        let err = registered.solve(input.as_ref(), 1, None).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SolveError>(),
+            Some(&SolveError::EmptyInput)
+        );
+    }
+}
@@ -0,0 +1,624 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+
+use crate::fetch::{self, Config};
+use crate::solution::Answer;
+
+pub const DEFAULT_SUBMISSIONS_FILE: &str = "submissions.csv";
+
+const HEADER: &str = "timestamp,year,day,part,outcome,wait,answer";
+
+// How long the server makes you wait after a wrong answer, when the page doesn't say
+const DEFAULT_WAIT: u64 = 60;
+
+// What the server made of a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without saying which way
+    Incorrect,
+    // Submitted too soon after the last answer, with the seconds left to wait
+    WaitSeconds(u64),
+    // The part was already solved, so the answer wasn't checked
+    AlreadySolved,
+}
+
+impl Outcome {
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::WaitSeconds(_) => "wait",
+            Outcome::AlreadySolved => "already-solved",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Incorrect => write!(f, "not the right answer"),
+            Outcome::WaitSeconds(seconds) => write!(f, "submitted too soon, wait {}s", seconds),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+// The server's reply to an answer: the outcome, and how many seconds must pass before
+// the next answer will be accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    pub wait: u64,
+}
+
+// Make sense of the page the server returns for an answer. Only the text of its
+// <article> is looked at, with the markup stripped.
+pub fn parse_reply(page: &str) -> Result<Reply> {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let text = tags.replace_all(article, "");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let reply = |outcome, wait| Ok(Reply { outcome, wait });
+    if text.contains("That's the right answer") {
+        return reply(Outcome::Correct, 0);
+    }
+    if text.contains("You don't seem to be solving the right level") {
+        return reply(Outcome::AlreadySolved, 0);
+    }
+    if text.contains("You gave an answer too recently") {
+        let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let seconds = left
+            .captures(&text)
+            .map(|captures| {
+                let number = |index| {
+                    captures
+                        .get(index)
+                        .map_or(0, |m| m.as_str().parse().unwrap())
+                };
+                number(1) * 60 + number(2)
+            })
+            .unwrap_or(DEFAULT_WAIT);
+        return reply(Outcome::WaitSeconds(seconds), seconds);
+    }
+    if text.contains("That's not the right answer") {
+        let outcome = if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        };
+        let minutes = Regex::new(r"(?i)wait (one|\d+) minutes? before trying again").unwrap();
+        let wait = minutes
+            .captures(&text)
+            .map(|captures| match &captures[1] {
+                "one" => 60,
+                minutes => minutes.parse::<u64>().unwrap() * 60,
+            })
+            .unwrap_or(DEFAULT_WAIT);
+        return reply(outcome, wait);
+    }
+    Err(anyhow!("unrecognised reply: {}", text))
+}
+
+// One answer sent to the server. The log is a CSV file with one submission per line,
+// appended to like the benchmark history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub reply: Reply,
+}
+
+impl Submission {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.year,
+            self.day,
+            self.part,
+            self.reply.outcome.name(),
+            self.reply.wait,
+            escape(&self.answer.to_string())
+        )
+    }
+
+    // The answer is the last field, so it may contain commas
+    fn from_csv(line: &str) -> Result<Submission> {
+        let fields: Vec<&str> = line.splitn(7, ',').collect();
+        if fields.len() != 7 {
+            return Err(anyhow!("expected 7 fields, found {}", fields.len()));
+        }
+        let number = |index: usize| {
+            fields[index]
+                .parse::<u64>()
+                .map_err(|_| anyhow!("expected a number, found {}", fields[index]))
+        };
+        let wait = number(5)?;
+        let outcome = match fields[4] {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "incorrect" => Outcome::Incorrect,
+            "wait" => Outcome::WaitSeconds(wait),
+            "already-solved" => Outcome::AlreadySolved,
+            other => return Err(anyhow!("unknown outcome {}", other)),
+        };
+        let answer = match fields[6].parse::<i64>() {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(unescape(fields[6])),
+        };
+        Ok(Submission {
+            timestamp: number(0)?,
+            year: number(1)? as u32,
+            day: number(2)? as u32,
+            part: number(3)? as u32,
+            answer,
+            reply: Reply { outcome, wait },
+        })
+    }
+
+    fn is_for(&self, year: u32, day: u32, part: u32) -> bool {
+        (self.year, self.day, self.part) == (year, day, part)
+    }
+}
+
+// Write newlines in an answer as \n, and backslashes as \\, so a multi-line answer
+// stays on one line of the log
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(field: &str) -> String {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('n')) => {
+                answer.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                answer.push('\\');
+                chars.next();
+            }
+            _ => answer.push(c),
+        }
+    }
+    answer
+}
+
+// Read every submission in the log. A missing file is an empty log.
+pub fn load(path: &Path) -> Result<Vec<Submission>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).with_context(|| format!("could not read {}", path.display())),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+        .map(|(index, line)| {
+            Submission::from_csv(line)
+                .with_context(|| format!("{} line {}", path.display(), index + 1))
+        })
+        .collect()
+}
+
+// Append a submission to the log, writing the header first if the file is new
+pub fn append(path: &Path, submission: &Submission) -> Result<()> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("could not open {}", path.display()))?;
+    let mut contents = String::new();
+    if new {
+        contents.push_str(HEADER);
+        contents.push('\n');
+    }
+    contents.push_str(&submission.to_csv());
+    contents.push('\n');
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("could not write {}", path.display()))
+}
+
+// Why `answer` shouldn't be sent at `now`, going by the log: the part is already solved,
+// the answer is already known to be wrong, or the server asked for a wait that isn't over
+pub fn refusal(
+    log: &[Submission],
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &Answer,
+    now: u64,
+) -> Option<String> {
+    let previous: Vec<&Submission> = log
+        .iter()
+        .filter(|submission| submission.is_for(year, day, part))
+        .collect();
+
+    for submission in &previous {
+        match submission.reply.outcome {
+            Outcome::Correct if &submission.answer == answer => {
+                return Some(format!("{} is already known to be correct", answer))
+            }
+            Outcome::Correct => {
+                return Some(format!(
+                    "already solved, the answer was {}",
+                    submission.answer
+                ))
+            }
+            Outcome::AlreadySolved => return Some("already solved".to_string()),
+            outcome if outcome.is_wrong() && &submission.answer == answer => {
+                return Some(format!(
+                    "{} was already submitted and was {}",
+                    answer, outcome
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    // A number can be ruled out by any earlier answer that was too high or too low
+    if let Answer::Number(n) = answer {
+        for submission in &previous {
+            let Answer::Number(earlier) = submission.answer else {
+                continue;
+            };
+            match submission.reply.outcome {
+                Outcome::TooHigh if *n >= earlier => {
+                    return Some(format!("{} is too high, {} already was", n, earlier))
+                }
+                Outcome::TooLow if *n <= earlier => {
+                    return Some(format!("{} is too low, {} already was", n, earlier))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // The server throttles every answer for the year, not just the part's
+    let ready = log
+        .iter()
+        .filter(|submission| submission.year == year)
+        .map(|submission| submission.timestamp + submission.reply.wait)
+        .max()?;
+    (ready > now).then(|| format!("wait {}s more before submitting", ready - now))
+}
+
+// Send an answer to the server, as long as the log doesn't rule it out, and record
+// what came back
+pub fn submit(
+    config: &Config,
+    log: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &Answer,
+    now: u64,
+) -> Result<Submission> {
+    if let Some(reason) = refusal(&load(log)?, year, day, part, answer, now) {
+        return Err(anyhow!("refusing to submit: {}", reason));
+    }
+    let session = config.session()?;
+    let url = config.url(year, day, "answer");
+    let response = fetch::agent()
+        .post(&url)
+        .header("Cookie", &format!("session={}", session))
+        .header("User-Agent", &config.user_agent())
+        .send_form([("level", part.to_string()), ("answer", answer.to_string())]);
+    let page = match response {
+        Ok(mut response) => response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("could not read the response from {}", url))?,
+        Err(ureq::Error::StatusCode(status)) if fetch::is_logged_out(status) => {
+            return Err(anyhow!(
+                "{} returned {}, the session token has probably expired",
+                url,
+                status
+            ))
+        }
+        Err(err) => return Err(err).with_context(|| format!("could not submit to {}", url)),
+    };
+
+    let submission = Submission {
+        timestamp: now,
+        year,
+        day,
+        part,
+        answer: answer.clone(),
+        reply: parse_reply(&page)?,
+    };
+    append(log, &submission)?;
+    Ok(submission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::stub;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    const RIGHT: &str = "That's the right answer!  You are <em>one gold star</em> closer to \
+        saving your vacation. <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a>";
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high.  If you're \
+        stuck, make sure you're using the full input data. Please wait one minute before \
+        trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>";
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low.  Because you \
+        have guessed incorrectly 5 times on this puzzle, please wait 5 minutes before trying \
+        again.";
+    const WRONG: &str = "That's not the right answer.  If you're stuck, make sure you're using \
+        the full input data.";
+    const TOO_RECENT: &str = "You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 12s left to wait.";
+    const SOLVED: &str = "You don't seem to be solving the right level.  Did you already \
+        complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a>";
+
+    #[test]
+    fn test_parse_reply() {
+        struct TestCase {
+            description: &'static str,
+            page: String,
+            expected: Option<Reply>,
+        }
+        let reply = |outcome, wait| Some(Reply { outcome, wait });
+        let cases = vec![
+            TestCase {
+                description: "right",
+                page: page(RIGHT),
+                expected: reply(Outcome::Correct, 0),
+            },
+            TestCase {
+                description: "too high",
+                page: page(TOO_HIGH),
+                expected: reply(Outcome::TooHigh, 60),
+            },
+            TestCase {
+                description: "too low, longer wait",
+                page: page(TOO_LOW),
+                expected: reply(Outcome::TooLow, 300),
+            },
+            TestCase {
+                description: "wrong, no wait given",
+                page: page(WRONG),
+                expected: reply(Outcome::Incorrect, DEFAULT_WAIT),
+            },
+            TestCase {
+                description: "too recent",
+                page: page(TOO_RECENT),
+                expected: reply(Outcome::WaitSeconds(72), 72),
+            },
+            TestCase {
+                description: "already solved",
+                page: page(SOLVED),
+                expected: reply(Outcome::AlreadySolved, 0),
+            },
+            TestCase {
+                description: "something else",
+                page: page("Puzzle inputs differ by user."),
+                expected: None,
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                parse_reply(&case.page).ok(),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+    }
+
+    fn submission(timestamp: u64, part: u32, answer: Answer, outcome: Outcome) -> Submission {
+        let wait = match outcome {
+            Outcome::WaitSeconds(seconds) => seconds,
+            outcome if outcome.is_wrong() => 60,
+            _ => 0,
+        };
+        Submission {
+            timestamp,
+            year: 2022,
+            day: 1,
+            part,
+            answer,
+            reply: Reply { outcome, wait },
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let submissions = [
+            submission(1000, 1, Answer::Number(-5), Outcome::TooLow),
+            submission(
+                1000,
+                2,
+                Answer::Text("a,b".to_string()),
+                Outcome::WaitSeconds(30),
+            ),
+            submission(
+                1000,
+                2,
+                Answer::Text("#..#\n.##.\\n".to_string()),
+                Outcome::Incorrect,
+            ),
+        ];
+        for submission in submissions {
+            let line = submission.to_csv();
+            assert!(!line.contains('\n'), "{}", line);
+            assert_eq!(Submission::from_csv(&line).unwrap(), submission, "{}", line);
+        }
+        assert!(Submission::from_csv("1000,2022,1,1,maybe,0,5").is_err());
+    }
+
+    #[test]
+    fn test_refusal() {
+        struct TestCase {
+            description: &'static str,
+            log: Vec<Submission>,
+            part: u32,
+            answer: i64,
+            now: u64,
+            expected: Option<&'static str>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "nothing submitted",
+                log: vec![],
+                part: 1,
+                answer: 10,
+                now: 1000,
+                expected: None,
+            },
+            TestCase {
+                description: "already known wrong",
+                log: vec![submission(0, 1, Answer::Number(10), Outcome::Incorrect)],
+                part: 1,
+                answer: 10,
+                now: 1000,
+                expected: Some("10 was already submitted and was not the right answer"),
+            },
+            TestCase {
+                description: "above an answer that was too high",
+                log: vec![submission(0, 1, Answer::Number(10), Outcome::TooHigh)],
+                part: 1,
+                answer: 12,
+                now: 1000,
+                expected: Some("12 is too high, 10 already was"),
+            },
+            TestCase {
+                description: "between too low and too high",
+                log: vec![
+                    submission(0, 1, Answer::Number(10), Outcome::TooHigh),
+                    submission(100, 1, Answer::Number(5), Outcome::TooLow),
+                ],
+                part: 1,
+                answer: 7,
+                now: 1000,
+                expected: None,
+            },
+            TestCase {
+                description: "solved",
+                log: vec![submission(0, 1, Answer::Number(7), Outcome::Correct)],
+                part: 1,
+                answer: 8,
+                now: 1000,
+                expected: Some("already solved, the answer was 7"),
+            },
+            TestCase {
+                description: "cooling down after another part",
+                log: vec![submission(990, 2, Answer::Number(3), Outcome::TooLow)],
+                part: 1,
+                answer: 8,
+                now: 1000,
+                expected: Some("wait 50s more before submitting"),
+            },
+            TestCase {
+                description: "told to wait",
+                log: vec![submission(
+                    900,
+                    1,
+                    Answer::Number(3),
+                    Outcome::WaitSeconds(200),
+                )],
+                part: 1,
+                answer: 3,
+                now: 1000,
+                expected: Some("wait 100s more before submitting"),
+            },
+            TestCase {
+                description: "cooldown over",
+                log: vec![submission(900, 1, Answer::Number(3), Outcome::TooLow)],
+                part: 1,
+                answer: 8,
+                now: 1000,
+                expected: None,
+            },
+        ];
+        for case in cases {
+            let answer = Answer::Number(case.answer);
+            assert_eq!(
+                refusal(&case.log, 2022, 1, case.part, &answer, case.now).as_deref(),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let log = std::env::temp_dir().join(format!("aoc-submit-test-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&log);
+        let (url, requests) = stub::server(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let config = Config {
+            session: Some("abc123".to_string()),
+            base_url: url,
+            contact: None,
+        };
+
+        let submitted = submit(&config, &log, 2022, 1, 1, &Answer::Number(50), 1000).unwrap();
+        assert_eq!(submitted.reply.outcome, Outcome::TooHigh);
+        let request = requests.recv().unwrap();
+        assert_eq!(request.lines[0], "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.body, "level=1&answer=50");
+
+        // Refused without asking the server: the same answer, a higher one, and
+        // anything inside the minute's wait
+        for (answer, now) in [(50, 2000), (60, 2000), (40, 1030)] {
+            let err = submit(&config, &log, 2022, 1, 1, &Answer::Number(answer), now);
+            assert!(err.is_err(), "{} at {}", answer, now);
+        }
+
+        let submitted = submit(&config, &log, 2022, 1, 1, &Answer::Number(40), 2000).unwrap();
+        assert_eq!(submitted.reply.outcome, Outcome::Correct);
+        assert_eq!(requests.recv().unwrap().body, "level=1&answer=40");
+
+        let outcomes: Vec<Outcome> = load(&log)
+            .unwrap()
+            .iter()
+            .map(|submission| submission.reply.outcome)
+            .collect();
+        assert_eq!(outcomes, vec![Outcome::TooHigh, Outcome::Correct]);
+        fs::remove_file(&log).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use crate::bench::{self, format_duration};
+
+// How many of the slowest parts the summary points out
+pub const SLOWEST: usize = 3;
+
+// How one part went when every part was run together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    // pass, FAIL or missing, as check reports them
+    pub status: &'static str,
+    // The answer, or why there isn't one
+    pub answer: String,
+    // How long parsing and solving took, if the part ran at all
+    pub timing: Option<(Duration, Duration)>,
+}
+
+impl Entry {
+    fn total(&self) -> Option<Duration> {
+        self.timing
+            .map(|(generator, solution)| generator + solution)
+    }
+}
+
+// Render a table of every entry in order, marking the slowest, followed by the totals
+pub fn report(entries: &[Entry], wall: Duration, workers: usize) -> String {
+    let mut by_time: Vec<(usize, Duration)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| Some((index, entry.total()?)))
+        .collect();
+    by_time.sort_by_key(|&(index, total)| (std::cmp::Reverse(total), index));
+    let slowest: Vec<usize> = by_time
+        .iter()
+        .take(SLOWEST)
+        .map(|&(index, _)| index)
+        .collect();
+
+    let time = |duration: Option<Duration>| duration.map_or("-".to_string(), format_duration);
+    let mut cells = vec![vec![
+        "Solution".to_string(),
+        "Status".to_string(),
+        "Answer".to_string(),
+        "Generator".to_string(),
+        "Solution".to_string(),
+        "Total".to_string(),
+        "Slowest".to_string(),
+    ]];
+    cells.extend(entries.iter().enumerate().map(|(index, entry)| {
+        vec![
+            entry.name.clone(),
+            entry.status.to_string(),
+            first_line(&entry.answer),
+            time(entry.timing.map(|(generator, _)| generator)),
+            time(entry.timing.map(|(_, solution)| solution)),
+            time(entry.total()),
+            slowest
+                .iter()
+                .position(|&slow| slow == index)
+                .map_or(String::new(), |rank| format!("#{}", rank + 1)),
+        ]
+    }));
+    let mut report = bench::markdown(&cells);
+
+    let count = |status| {
+        entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    };
+    let busy: Duration = by_time.iter().map(|&(_, total)| total).sum();
+    report += &format!(
+        "\nRan {} parts on {} worker{} in {} wall time ({} spent solving)\n",
+        entries.len(),
+        workers,
+        if workers == 1 { "" } else { "s" },
+        format_duration(wall),
+        format_duration(busy)
+    );
+    if !slowest.is_empty() {
+        let names: Vec<String> = slowest
+            .iter()
+            .map(|&index| {
+                let entry = &entries[index];
+                format!("{} ({})", entry.name, time(entry.total()))
+            })
+            .collect();
+        report += &format!("Slowest: {}\n", names.join(", "));
+    }
+    report += &format!(
+        "{} passed, {} failed, {} missing\n",
+        count("pass"),
+        count("FAIL"),
+        count("missing")
+    );
+    report
+}
+
+// Multi-line answers and errors would break the table, so only their first line is shown
+fn first_line(text: &str) -> String {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or("");
+    match lines.next() {
+        Some(_) => format!("{} ...", first),
+        None => first.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_report() {
+        let entry = |day, status, answer: &str, timing| Entry {
+            name: format!("2022 Day {} - Part 1", day),
+            status,
+            answer: answer.to_string(),
+            timing,
+        };
+        let entries = vec![
+            entry(1, "pass", "24000", Some((millis(1), millis(2)))),
+            entry(2, "FAIL", "15", Some((millis(1), millis(40)))),
+            entry(3, "missing", "no input at input/2022/day3.txt", None),
+            entry(4, "pass", "2", Some((millis(5), millis(5)))),
+            entry(
+                5,
+                "pass",
+                "line one\nline two",
+                Some((millis(2), millis(0))),
+            ),
+        ];
+        assert_eq!(
+            report(&entries, millis(45), 4),
+            "\
+| Solution            | Status  | Answer                          | Generator | Solution | Total    | Slowest |
+| ------------------- | ------- | ------------------------------- | --------- | -------- | -------- | ------- |
+| 2022 Day 1 - Part 1 | pass    | 24000                           | 1.000 ms  | 2.000 ms | 3.000 ms | #3      |
+| 2022 Day 2 - Part 1 | FAIL    | 15                              | 1.000 ms  | 40.00 ms | 41.00 ms | #1      |
+| 2022 Day 3 - Part 1 | missing | no input at input/2022/day3.txt | -         | -        | -        |         |
+| 2022 Day 4 - Part 1 | pass    | 2                               | 5.000 ms  | 5.000 ms | 10.00 ms | #2      |
+| 2022 Day 5 - Part 1 | pass    | line one ...                    | 2.000 ms  | 0.000 ns | 2.000 ms |         |
+
+Ran 5 parts on 4 workers in 45.00 ms wall time (56.00 ms spent solving)
+Slowest: 2022 Day 2 - Part 1 (41.00 ms), 2022 Day 4 - Part 1 (10.00 ms), 2022 Day 1 - Part 1 (3.000 ms)
+3 passed, 1 failed, 1 missing
+"
+        );
+
+        assert_eq!(
+            report(&[], millis(0), 1),
+            "\
+| Solution | Status | Answer | Generator | Solution | Total | Slowest |
+| -------- | ------ | ------ | --------- | -------- | ----- | ------- |
+
+Ran 0 parts on 1 worker in 0.000 ns wall time (0.000 ns spent solving)
+0 passed, 0 failed, 0 missing
+"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+
+// False when built without the `trace` feature, as benchmarks are, which turns every
+// trace macro into dead code the compiler removes
+pub const ENABLED: bool = cfg!(feature = "trace");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Level> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.name() == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown trace level {}, expected error, warn, info, debug or trace",
+                    name
+                )
+            })
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name().to_uppercase())
+    }
+}
+
+// Which targets are traced, and how verbosely. Targets are module paths, and are named
+// by their last segments, so `day11` and `year2022::day11` both select
+// advent_of_code::year2022::day11.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Filter {
+    // The level for targets without a directive of their own
+    default: Option<Level>,
+    directives: Vec<(String, Level)>,
+}
+
+impl Filter {
+    // Read a comma separated list of `target=level` directives, where a bare level sets
+    // the default, like `day11=debug,day8=trace` or `info,day12=trace`
+    pub fn parse(spec: &str) -> Result<Filter> {
+        let mut filter = Filter::default();
+        for directive in spec.split(',').map(str::trim) {
+            match directive.split_once('=') {
+                Some((target, level)) if !target.is_empty() => {
+                    filter.directives.push((target.to_string(), level.parse()?));
+                }
+                Some(_) => return Err(anyhow!("missing the target in {:?}", directive)),
+                None => filter.default = Some(directive.parse()?),
+            }
+        }
+        Ok(filter)
+    }
+
+    // The most verbose level traced for a target. The longest matching directive wins.
+    pub fn level(&self, target: &str) -> Option<Level> {
+        self.directives
+            .iter()
+            .filter(|(name, _)| target == name || target.ends_with(&format!("::{}", name)))
+            .max_by_key(|(name, _)| name.len())
+            .map(|&(_, level)| level)
+            .or(self.default)
+    }
+
+    fn max_level(&self) -> Option<Level> {
+        let directives = self.directives.iter().map(|&(_, level)| level);
+        directives.chain(self.default).max()
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+// The most verbose level of any target, so events nobody asked for cost a single load
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+// Start tracing with `filter`. Can only be called once, before anything is traced.
+pub fn init(filter: Filter) -> Result<()> {
+    if !ENABLED {
+        return Err(anyhow!(
+            "tracing was compiled out, build with --features trace"
+        ));
+    }
+    let max_level = filter.max_level().map_or(0, |level| level as u8);
+    FILTER
+        .set(filter)
+        .map_err(|_| anyhow!("tracing is already initialised"))?;
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+    Ok(())
+}
+
+#[inline]
+pub fn enabled(level: Level, target: &str) -> bool {
+    ENABLED
+        && level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+        && FILTER
+            .get()
+            .and_then(|filter| filter.level(target))
+            .is_some_and(|max| level <= max)
+}
+
+// Write an event to stderr, prefixing each of its lines with where it came from
+pub fn emit(level: Level, target: &str, message: fmt::Arguments) {
+    let target = target.split_once("::").map_or(target, |(_, rest)| rest);
+    for line in message.to_string().lines() {
+        eprintln!("[{} {}] {}", target, level, line);
+    }
+}
+
+// Trace an event at a level, formatted like println!, if the calling module is traced
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level, module_path!()) {
+            $crate::trace::emit($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+// Whether the calling module is traced at a level, to skip preparing what would be traced
+#[macro_export]
+macro_rules! tracing {
+    ($level:expr) => {
+        $crate::trace::enabled($level, module_path!())
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        struct TestCase {
+            description: &'static str,
+            spec: &'static str,
+            target: &'static str,
+            expected: Option<Level>,
+        }
+        let cases = vec![
+            TestCase {
+                description: "named day",
+                spec: "day11=debug",
+                target: "advent_of_code::year2022::day11",
+                expected: Some(Level::Debug),
+            },
+            TestCase {
+                description: "a day is not a prefix of another",
+                spec: "day1=debug",
+                target: "advent_of_code::year2022::day11",
+                expected: None,
+            },
+            TestCase {
+                description: "year and day",
+                spec: "year2022::day11=trace",
+                target: "advent_of_code::year2022::day11",
+                expected: Some(Level::Trace),
+            },
+            TestCase {
+                description: "other year",
+                spec: "year2023::day11=trace",
+                target: "advent_of_code::year2022::day11",
+                expected: None,
+            },
+            TestCase {
+                description: "default",
+                spec: "info",
+                target: "advent_of_code::year2022::day8",
+                expected: Some(Level::Info),
+            },
+            TestCase {
+                description: "most specific wins",
+                spec: "warn, year2022::day8=trace, day8=error",
+                target: "advent_of_code::year2022::day8",
+                expected: Some(Level::Trace),
+            },
+        ];
+        for case in cases {
+            let filter = Filter::parse(case.spec).unwrap();
+            assert_eq!(
+                filter.level(case.target),
+                case.expected,
+                "{}",
+                case.description
+            );
+        }
+
+        for spec in ["day11=loud", "=debug", "day11", ""] {
+            assert!(Filter::parse(spec).is_err(), "{:?}", spec);
+        }
+    }
+
+    #[test]
+    fn test_max_level() {
+        let filter = Filter::parse("warn,day8=debug,day6=info").unwrap();
+        assert_eq!(filter.max_level(), Some(Level::Debug));
+        assert_eq!(Filter::default().max_level(), None);
+    }
+}
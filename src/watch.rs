@@ -0,0 +1,408 @@
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+use crate::bench::format_duration;
+use crate::input::Source;
+use crate::samples::{self, SAMPLES_DIR};
+
+// Editors save in several steps, so changes are collected for this long before rebuilding
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+// A file, or every sample file of a day, that triggers a new round when it changes.
+// The directory is watched rather than the file, because editors often save by writing
+// a new file and renaming it over the old one, which a watch on the file itself misses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub dir: PathBuf,
+    pub name: Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Name {
+    File(OsString),
+    // dayN.txt and dayN-name.txt
+    Samples(u32),
+}
+
+impl Target {
+    fn file(path: &Path) -> Target {
+        let dir = match path.parent() {
+            Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        Target {
+            dir,
+            name: Name::File(path.file_name().unwrap_or_default().to_os_string()),
+        }
+    }
+
+    pub fn matches(&self, name: &OsStr) -> bool {
+        match &self.name {
+            Name::File(file) => file == name,
+            Name::Samples(day) => samples::sample_day(Path::new(name)) == Some(*day),
+        }
+    }
+}
+
+// The day's module, its samples and its input
+pub fn targets(year: u32, day: u32, input: &Path) -> Vec<Target> {
+    vec![
+        Target::file(&Path::new("src").join(format!("year{}/day{}.rs", year, day))),
+        Target {
+            dir: Path::new(SAMPLES_DIR).join(year.to_string()),
+            name: Name::Samples(day),
+        },
+        Target::file(input),
+    ]
+}
+
+// What one round made of one part, read back from the runner's JSON output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    // The sample file, or None for the puzzle input
+    pub sample: Option<String>,
+    // Like "Part 1" or "Part 1 - astar"
+    pub name: String,
+    pub answer: Option<String>,
+    pub status: Option<String>,
+    pub nanos: Option<u64>,
+    pub error: Option<String>,
+}
+
+// Read the records `run` and `check` print with --format json, skipping any other line
+pub fn parse_outcomes(output: &str) -> Vec<Outcome> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| {
+            let text = |key: &str| match &record[key] {
+                serde_json::Value::String(text) => Some(text.clone()),
+                serde_json::Value::Null => None,
+                other => Some(other.to_string()),
+            };
+            let name = match text("variant") {
+                Some(variant) => format!("Part {} - {}", record["part"].as_u64()?, variant),
+                None => format!("Part {}", record["part"].as_u64()?),
+            };
+            let nanos = match (record["parse_ns"].as_u64(), record["solve_ns"].as_u64()) {
+                (Some(parse), Some(solve)) => Some(parse + solve),
+                _ => None,
+            };
+            Some(Outcome {
+                sample: text("sample"),
+                name,
+                answer: text("answer"),
+                status: text("status"),
+                nanos,
+                error: record["error"]["message"].as_str().map(String::from),
+            })
+        })
+        .collect()
+}
+
+// One line per outcome, grouped by input, saying what changed since the previous round
+pub fn report(previous: &[Outcome], current: &[Outcome]) -> String {
+    let mut report = String::new();
+    let mut group = None;
+    for outcome in current {
+        if group != Some(&outcome.sample) {
+            group = Some(&outcome.sample);
+            report += &format!("{}\n", outcome.sample.as_deref().unwrap_or("input"));
+        }
+        let before = previous
+            .iter()
+            .find(|before| (&before.sample, &before.name) == (&outcome.sample, &outcome.name));
+        report += &format!("    {}\n", line(before, outcome));
+    }
+    report
+}
+
+fn line(before: Option<&Outcome>, outcome: &Outcome) -> String {
+    let status = match outcome.status.as_deref() {
+        Some("pass") => "pass",
+        Some("fail") => "FAIL",
+        Some("missing") => "missing",
+        _ => "",
+    };
+    let answer = match (&outcome.error, &outcome.answer) {
+        (Some(error), _) => format!("error: {}", first_line(error)),
+        (None, Some(answer)) => match before.and_then(|before| before.answer.as_ref()) {
+            Some(was) if was != answer => {
+                format!("{} (was {})", first_line(answer), first_line(was))
+            }
+            _ => first_line(answer).to_string(),
+        },
+        (None, None) => "-".to_string(),
+    };
+    let time = match (before.and_then(|before| before.nanos), outcome.nanos) {
+        (Some(was), Some(nanos)) if was > 0 => format!(
+            "{} ({:+.1}%)",
+            format_duration(Duration::from_nanos(nanos)),
+            (nanos as f64 - was as f64) / was as f64 * 100.0
+        ),
+        (_, Some(nanos)) => format_duration(Duration::from_nanos(nanos)),
+        (_, None) => String::new(),
+    };
+    format!("{:<24} {:<7} {:<24} {}", outcome.name, status, answer, time)
+        .trim_end()
+        .to_string()
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
+}
+
+// Rebuild whenever the day's module, samples or input change, then check the samples and
+// run the real input, printing how the answers and timings moved. Runs until interrupted.
+pub fn watch(year: u32, day: u32, input: Option<&str>, input_dir: Option<&Path>) -> Result<()> {
+    let Source::File(input_path) = Source::resolve(year, day, input, input_dir) else {
+        return Err(anyhow!(
+            "watch can't re-read stdin, pass --input <PATH> instead"
+        ));
+    };
+    let targets = targets(year, day, &input_path);
+
+    let mut inotify = Inotify::init().context("could not start inotify")?;
+    let mut watches: HashMap<WatchDescriptor, Vec<usize>> = HashMap::new();
+    let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::DELETE;
+    for (index, target) in targets.iter().enumerate() {
+        if !target.dir.is_dir() {
+            eprintln!(
+                "warning: {} does not exist, not watching it",
+                target.dir.display()
+            );
+            continue;
+        }
+        let descriptor = inotify
+            .watches()
+            .add(&target.dir, mask)
+            .with_context(|| format!("could not watch {}", target.dir.display()))?;
+        watches.entry(descriptor).or_default().push(index);
+    }
+
+    let day_args = vec![
+        "--year".to_string(),
+        year.to_string(),
+        "--day".to_string(),
+        day.to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ];
+    let mut sample_args = day_args.clone();
+    sample_args.push("--samples".to_string());
+    let mut input_args = day_args;
+    if let Some(input) = input {
+        input_args.extend(["--input".to_string(), input.to_string()]);
+    }
+    if let Some(dir) = input_dir {
+        input_args.extend(["--input-dir".to_string(), dir.display().to_string()]);
+    }
+
+    let mut previous = vec![];
+    let mut reason = "Starting".to_string();
+    loop {
+        println!("== {}", reason);
+        match build()? {
+            Some(errors) => eprint!("{}", errors),
+            None => {
+                let mut current = runner("check", &sample_args)?;
+                current.extend(runner("run", &input_args)?);
+                print!("{}", report(&previous, &current));
+                previous = current;
+            }
+        }
+        println!();
+
+        let changed = wait(&mut inotify, &watches, &targets)?;
+        let changed: Vec<String> = changed
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        reason = format!("{} changed", changed.join(", "));
+    }
+}
+
+// The cargo running us, so the same toolchain rebuilds
+fn cargo() -> Command {
+    Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+// Build the runner, returning the compiler's output if the build failed
+fn build() -> Result<Option<String>> {
+    let output = cargo()
+        .args(["build", "--release", "--color", "always"])
+        .stdout(Stdio::null())
+        .output()
+        .context("could not run cargo build")?;
+    Ok(match output.status.success() {
+        true => None,
+        false => Some(String::from_utf8_lossy(&output.stderr).into_owned()),
+    })
+}
+
+// Run a command of the freshly built runner, letting its traces through on stderr
+fn runner(command: &str, args: &[String]) -> Result<Vec<Outcome>> {
+    let output = cargo()
+        .args(["run", "--release", "-q", "--", command])
+        .args(args)
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("could not run {}", command))?;
+    Ok(parse_outcomes(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Block until a target changes, returning the paths that changed
+fn wait(
+    inotify: &mut Inotify,
+    watches: &HashMap<WatchDescriptor, Vec<usize>>,
+    targets: &[Target],
+) -> Result<BTreeSet<PathBuf>> {
+    let mut buffer = [0; 4096];
+    let mut changed = BTreeSet::new();
+    let collect = |events: inotify::Events, changed: &mut BTreeSet<PathBuf>| {
+        for event in events {
+            let (Some(indices), Some(name)) = (watches.get(&event.wd), event.name) else {
+                continue;
+            };
+            for &index in indices {
+                if targets[index].matches(name) {
+                    changed.insert(targets[index].dir.join(name));
+                }
+            }
+        }
+    };
+
+    while changed.is_empty() {
+        collect(inotify.read_events_blocking(&mut buffer)?, &mut changed);
+    }
+    loop {
+        thread::sleep(DEBOUNCE);
+        match inotify.read_events(&mut buffer) {
+            Ok(events) => collect(events, &mut changed),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(changed),
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets() {
+        let targets = targets(2022, 1, Path::new("input/2022/day1.txt"));
+        assert_eq!(targets[0].dir, Path::new("src/year2022"));
+        assert_eq!(targets[1].dir, Path::new("samples/2022"));
+        assert_eq!(targets[2].dir, Path::new("input/2022"));
+
+        struct TestCase {
+            name: &'static str,
+            expected: [bool; 3],
+        }
+        let cases = vec![
+            TestCase {
+                name: "day1.rs",
+                expected: [true, false, false],
+            },
+            TestCase {
+                name: "day12.rs",
+                expected: [false, false, false],
+            },
+            TestCase {
+                name: "day1.txt",
+                expected: [false, true, true],
+            },
+            TestCase {
+                name: "day1-small.txt",
+                expected: [false, true, false],
+            },
+            TestCase {
+                name: "day11.txt",
+                expected: [false, false, false],
+            },
+            TestCase {
+                name: ".day1.rs.swp",
+                expected: [false, false, false],
+            },
+        ];
+        for case in cases {
+            let got: Vec<bool> = targets
+                .iter()
+                .map(|target| target.matches(OsStr::new(case.name)))
+                .collect();
+            assert_eq!(got, case.expected, "{}", case.name);
+        }
+
+        let targets = super::targets(2022, 1, Path::new("day1.txt"));
+        assert_eq!(targets[2].dir, Path::new("."));
+    }
+
+    fn outcome(sample: Option<&str>, name: &str, answer: &str, nanos: u64) -> Outcome {
+        Outcome {
+            sample: sample.map(String::from),
+            name: name.to_string(),
+            answer: Some(answer.to_string()),
+            status: sample.map(|_| "pass".to_string()),
+            nanos: Some(nanos),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_outcomes() {
+        let output = r#"{"schema":1,"command":"check","year":2022,"day":12,"part":1,"variant":"astar","sample":"samples/2022/day12.txt","answer":31,"status":"pass","expected":31,"parse_ns":1000,"solve_ns":2000,"parse_spread_ns":null,"solve_spread_ns":null,"error":null}
+not json
+{"schema":1,"command":"run","year":2022,"day":12,"part":2,"variant":null,"sample":null,"answer":null,"status":null,"expected":null,"parse_ns":null,"solve_ns":null,"parse_spread_ns":null,"solve_spread_ns":null,"error":{"kind":"input","message":"input file input/2022/day12.txt does not exist","diagnostic":null}}
+"#;
+        assert_eq!(
+            parse_outcomes(output),
+            vec![
+                outcome(Some("samples/2022/day12.txt"), "Part 1 - astar", "31", 3000),
+                Outcome {
+                    sample: None,
+                    name: "Part 2".to_string(),
+                    answer: None,
+                    status: None,
+                    nanos: None,
+                    error: Some("input file input/2022/day12.txt does not exist".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let sample = Some("samples/2022/day12.txt");
+        let previous = vec![
+            outcome(sample, "Part 1", "31", 2000),
+            outcome(None, "Part 1", "412", 10_000),
+        ];
+        let mut changed = outcome(sample, "Part 2", "30", 1000);
+        changed.status = Some("fail".to_string());
+        let current = vec![
+            outcome(sample, "Part 1", "31", 1000),
+            changed,
+            outcome(None, "Part 1", "408", 11_000),
+        ];
+        assert_eq!(
+            report(&previous, &current),
+            "\
+samples/2022/day12.txt
+    Part 1                   pass    31                       1.000 µs (-50.0%)
+    Part 2                   FAIL    30                       1.000 µs
+input
+    Part 1                           408 (was 412)            11.00 µs (+10.0%)
+"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
+
+type Calories = usize;
+
+struct Elf {
+    food: Vec<Calories>,
+}
+
+impl Elf {
+    // None if the calories add up to more than a Calories can hold
+    fn total(&self) -> Option<Calories> {
+        self.food
+            .iter()
+            .try_fold(0, |total: Calories, &food| total.checked_add(food))
+    }
+}
+
+// Split the input on blank lines, then parse each elf's inventory
+pub fn get_calorie_counts(input: &str) -> Result<Vec<Calories>, ParseError> {
+    let mut calorie_counts: Vec<Calories> = input
+        .split("\n\n")
+        .map(|inv| {
+            let food = inv
+                .split('\n')
+                .map(|food| parse::number(1, input, food, "expected a number of calories"))
+                .collect::<Result<Vec<Calories>, ParseError>>()?;
+            Elf { food }
+                .total()
+                .ok_or_else(|| ParseError::at(1, input, inv, "expected fewer calories in total"))
+        })
+        .collect::<Result<Vec<Calories>, ParseError>>()?;
+    calorie_counts.sort_by(|a, b| b.cmp(a));
+    Ok(calorie_counts)
+}
+
+pub fn solve_part1(calorie_counts: &[Calories]) -> Result<Calories, SolveError> {
+    calorie_counts
+        .first()
+        .copied()
+        .ok_or(SolveError::EmptyInput)
+}
+
+pub fn solve_part2(input: &[Calories]) -> Result<Calories, SolveError> {
+    if input.is_empty() {
+        return Err(SolveError::EmptyInput);
+    }
+    let Some(top_3) = input.get(0..3) else {
+        let detail = format!("expected at least 3 elves, found {}", input.len());
+        return Err(SolveError::invariant(detail));
+    };
+    top_3
+        .iter()
+        .try_fold(0, |sum: Calories, &calories| sum.checked_add(calories))
+        .ok_or_else(|| SolveError::invariant("the top 3 elves carry too many calories to add up"))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<Calories>;
+
+    fn parse(input: &str) -> Result<Vec<Calories>, ParseError> {
+        get_calorie_counts(input)
+    }
+
+    fn part1(input: &Vec<Calories>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Vec<Calories>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
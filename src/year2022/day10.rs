@@ -1,9 +1,12 @@
 use std::collections::VecDeque;
 
-use anyhow::{anyhow, Result};
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
+use crate::trace::Level;
+use crate::{debug, tracing};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Instruction {
+pub enum Instruction {
     NoOp,
     Add(char, isize),
 }
@@ -23,10 +26,10 @@ struct CpuEmulator {
     // Debugging features!
     // Run code at a specific clock tick or program step
     checkpoints: Vec<usize>, // Clock ticks at which to run the debug callback
-    clock_debug_callback: fn(&mut Self),
+    clock_debug_callback: fn(&mut Self) -> Result<(), SolveError>,
 
     breakpoints: Vec<usize>, // Program steps at which to run the debug callback
-    program_debug_callback: fn(&mut Self),
+    program_debug_callback: fn(&mut Self) -> Result<(), SolveError>,
 }
 
 impl CpuEmulator {
@@ -42,23 +45,24 @@ impl CpuEmulator {
             program: VecDeque::new(),
 
             checkpoints: vec![],
-            clock_debug_callback: |_| {},
+            clock_debug_callback: |_| Ok(()),
             breakpoints: vec![],
-            program_debug_callback: |_| {},
+            program_debug_callback: |_| Ok(()),
         }
     }
 
     // Step increments the CPU's program counter and
     // optionally runs a callback if there is a breakpoint at the current program counter.
-    fn clock_tick(&mut self) {
+    fn clock_tick(&mut self) -> Result<(), SolveError> {
         self.clock_counter += 1;
 
         if self.checkpoints.contains(&self.clock_counter) {
-            (self.clock_debug_callback)(self);
+            (self.clock_debug_callback)(self)?;
         }
+        Ok(())
     }
 
-    fn run(&mut self, program: &[Instruction]) {
+    fn run(&mut self, program: &[Instruction]) -> Result<(), SolveError> {
         self.program_counter = 0;
         self.program = VecDeque::from(program.to_owned());
 
@@ -68,97 +72,133 @@ impl CpuEmulator {
             self.program_counter += 1;
 
             if self.breakpoints.contains(&self.program_counter) {
-                (self.program_debug_callback)(self);
+                (self.program_debug_callback)(self)?;
             }
 
             match i {
-                Instruction::NoOp => self.noop(),
-                Instruction::Add(register, value) => self.add(register, value),
+                Instruction::NoOp => self.noop()?,
+                Instruction::Add(register, value) => self.add(register, value)?,
             }
         }
+        Ok(())
     }
 
-    fn noop(&mut self) {
-        self.clock_tick();
+    fn noop(&mut self) -> Result<(), SolveError> {
+        self.clock_tick()
     }
 
-    fn add(&mut self, register: char, value: isize) {
-        self.clock_tick();
-        self.clock_tick();
-        match register {
-            'x' => self.x_register += value,
-            'y' => self.y_register += value,
-            'z' => self.z_register += value,
-            _ => panic!("Invalid register: {}", register),
-        }
+    fn add(&mut self, register: char, value: isize) -> Result<(), SolveError> {
+        self.clock_tick()?;
+        self.clock_tick()?;
+        let target = match register {
+            'x' => &mut self.x_register,
+            'y' => &mut self.y_register,
+            'z' => &mut self.z_register,
+            _ => {
+                let detail = format!("expected register x, y or z, found {}", register);
+                return Err(SolveError::invariant(detail));
+            }
+        };
+        *target = target
+            .checked_add(value)
+            .ok_or_else(|| SolveError::invariant(format!("register {} overflowed", register)))?;
+        Ok(())
     }
 }
 
-fn parse_instruction(instruction: &str) -> Result<Instruction> {
+// Errors are reported as if the instruction were the whole input, the caller knows which line it is
+fn parse_instruction(instruction: &str) -> Result<Instruction, ParseError> {
     let parts: Vec<&str> = instruction.split_whitespace().collect();
+    let hint = "expected an instruction like \"noop\" or \"addx -3\"";
     // noop => NoOp
     // add* => Add('*', 0) where * could be any character in lower ASCII
     match parts.len() {
         1 if parts[0] == "noop" => Ok(Instruction::NoOp),
-        2 if &parts[0][0..3] == "add" && parts[0].len() == 4 => {
-            let register = parts[0][3..4]
+        2 if parts[0].starts_with("add") && parts[0].len() == 4 => {
+            let register = parts[0][3..]
                 .chars()
                 .next()
-                .ok_or_else(|| anyhow!("Invalid register: {}", parts[0]));
-            let value = parts[1]
-                .parse::<isize>()
-                .map_err(|_| anyhow!("Invalid value: {}", parts[1]));
-
-            match (register, value) {
-                (Ok(register), Ok(value)) => Ok(Instruction::Add(register, value)),
-                _ => Err(anyhow!("Invalid instruction: {}", instruction)),
-            }
+                .filter(char::is_ascii_lowercase)
+                .ok_or_else(|| {
+                    ParseError::at(10, instruction, parts[0], "expected a register like addx")
+                })?;
+            let value = parse::number::<isize>(10, instruction, parts[1], "expected a number")?;
+            Ok(Instruction::Add(register, value))
         }
-        _ => Err(anyhow!("Invalid instruction: {}", instruction)),
+        _ => Err(ParseError::at(10, instruction, instruction, hint)),
     }
 }
 
-#[aoc_generator(day10)]
-fn parse_input(input: &str) -> Vec<Instruction> {
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
     input
         .lines()
-        .map(|line| match parse_instruction(line) {
-            Ok(instruction) => instruction,
-            Err(err) => panic!("Error parsing instruction: {}", err),
-        })
-        .collect::<Vec<Instruction>>()
+        .enumerate()
+        .map(|(i, line)| parse_instruction(line).map_err(|err| err.on_line(i + 1)))
+        .collect()
 }
 
-#[aoc(day10, part1)]
-fn solve_part1(program: &[Instruction]) -> isize {
+fn solve_part1(program: &[Instruction]) -> Result<isize, SolveError> {
     let mut cpu = CpuEmulator::new();
 
     cpu.x_register = 1; // Starts at one per the problem statement
 
     cpu.checkpoints = vec![20, 60, 100, 140, 180, 220]; // Clock ticks at which to check the signal
     cpu.clock_debug_callback = |cpu| {
-        let strength = cpu.x_register * cpu.clock_counter as isize;
-        cpu.y_register += strength;
+        let overflowed = || SolveError::invariant("the signal strength overflowed");
+        let strength = cpu
+            .x_register
+            .checked_mul(cpu.clock_counter as isize)
+            .ok_or_else(overflowed)?;
+        cpu.y_register = cpu
+            .y_register
+            .checked_add(strength)
+            .ok_or_else(overflowed)?;
+        Ok(())
     };
 
-    // We could also run steps at specific program counters instead of clock ticks
-    // Eg, Display the state of the CPU at the end of the program
-    // cpu.breakpoints = vec![program.len()];
-    // cpu.program_debug_callback = |cpu| {
-    //     println!("Program counter: {}", cpu.program_counter);
-    //     println!("X register: {}", cpu.x_register);
-    //     println!("Y register: {}", cpu.y_register);
-    //     println!("Z register: {}", cpu.z_register);
-    // };
+    // Steps can also run at specific program counters instead of clock ticks
+    // Eg, Display the state of the CPU at the end of the program when debugging
+    if tracing!(Level::Debug) {
+        cpu.breakpoints = vec![program.len()];
+        cpu.program_debug_callback = |cpu| {
+            debug!("Program counter: {}", cpu.program_counter);
+            debug!("X register: {}", cpu.x_register);
+            debug!("Y register: {}", cpu.y_register);
+            debug!("Z register: {}", cpu.z_register);
+            Ok(())
+        };
+    }
+
+    cpu.run(program)?;
+
+    Ok(cpu.y_register)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const PARTS: &'static [u32] = &[1];
 
-    cpu.run(program);
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_input(input)
+    }
 
-    cpu.y_register
+    fn part1(input: &Vec<Instruction>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(_input: &Vec<Instruction>) -> Result<Answer, SolveError> {
+        Err(SolveError::Unsolved)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use anyhow::{anyhow, Result};
 
     #[test]
     fn test_parse_instructrion() {
@@ -0,0 +1,347 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools; // itertools = "0.8"
+
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
+use crate::trace::Level;
+use crate::{debug, tracing};
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug, Clone)]
+enum Magnitude {
+    SameAsBefore,
+    Factor(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: VecDeque<usize>,
+    operation: Operation,
+    magnitude: Magnitude,
+    test_value: usize,
+    true_target: usize,
+    false_target: usize,
+}
+
+impl Monkey {
+    fn run_turn(
+        &mut self,
+        worry_factor: Option<f64>,
+        lcm: usize,
+    ) -> Result<VecDeque<(usize, usize)>, SolveError> {
+        let mut item_updates = VecDeque::<(usize, usize)>::new();
+        while let Some(item_score) = self.items.pop_front() {
+            let magnitude = match self.magnitude {
+                Magnitude::Factor(n) => n,
+                Magnitude::SameAsBefore => item_score,
+            };
+            let item_score = match self.operation {
+                Operation::Add => item_score.checked_add(magnitude),
+                Operation::Multiply => item_score.checked_mul(magnitude),
+            }
+            .ok_or_else(|| SolveError::invariant("a worry level overflowed"))?;
+
+            let item_score = match worry_factor {
+                Some(n) => (item_score as f64 / n).floor() as usize,
+                None => {
+                    // Item score may be huge, so mod it against the lcm
+                    // This still allows us to select the correct target
+                    // because the lcm is a multiple of the test value
+                    item_score % lcm
+                }
+            };
+
+            let target = match item_score % self.test_value == 0 {
+                true => self.true_target,
+                false => self.false_target,
+            };
+
+            item_updates.push_back((target, item_score));
+        }
+        Ok(item_updates)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MonkeyBusinessTracker {
+    monkeys: HashMap<usize, Monkey>,
+    inspection_counts: HashMap<usize, usize>,
+}
+
+impl MonkeyBusinessTracker {
+    fn new_from_input(monkeys: HashMap<usize, Monkey>) -> Self {
+        MonkeyBusinessTracker {
+            monkeys,
+            inspection_counts: HashMap::<usize, usize>::new(),
+        }
+    }
+
+    fn update_activity(&mut self, monkey_id: usize, count: usize) {
+        self.inspection_counts
+            .entry(monkey_id)
+            .and_modify(|counter| *counter += count)
+            .or_insert(count);
+    }
+
+    fn update_items(&mut self, updates: VecDeque<(usize, usize)>) {
+        for (monkey_id, item_score) in updates {
+            let target_monkey = self.monkeys.get_mut(&monkey_id).unwrap();
+            target_monkey.items.push_back(item_score);
+        }
+    }
+
+    fn trace_items(&self) {
+        for (monkey_id, monkey) in self.monkeys.iter() {
+            debug!("Monkey {}: {:?}", monkey_id, monkey.items);
+        }
+    }
+
+    fn trace_inspections(&self) {
+        for monkey_id in self.inspection_counts.keys().sorted() {
+            debug!(
+                "Monkey {} has inspected {:?} items",
+                monkey_id,
+                self.inspection_counts.get(monkey_id).unwrap()
+            );
+        }
+    }
+
+    fn calculate(&self, n: usize) -> usize {
+        // Find the ids of the top two inspection counts
+        self.inspection_counts
+            .values()
+            .sorted()
+            .rev()
+            .take(n)
+            .copied()
+            .product()
+    }
+
+    // Calculate the least common multiple of all the test values
+    // This allows us to select targets without worrying about overflow
+    fn get_lcm(&self) -> Result<usize, SolveError> {
+        self.monkeys
+            .values()
+            .try_fold(1, |lcm: usize, monkey| lcm.checked_mul(monkey.test_value))
+            .ok_or_else(|| SolveError::invariant("the product of the divisors overflowed"))
+    }
+}
+
+// The text after `prefix` on the next line of a monkey's description,
+// `example` shows what the line should look like
+fn next_field<'a>(
+    input: &'a str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    prefix: &str,
+    example: &str,
+) -> Result<&'a str, ParseError> {
+    let hint = format!("expected a line like {:?}", example);
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::at(11, input, parse::end(input), &hint))?;
+    line.trim_start()
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(11, input, line, &hint))
+}
+
+// Parse an Operation from a string like "* 19" or "+ old"
+fn capture_operation(input: &str, operation: &str) -> Result<(Operation, Magnitude), ParseError> {
+    let (symbol, magnitude) = operation.split_once(' ').ok_or_else(|| {
+        ParseError::at(11, input, operation, "expected an operation like \"* 19\"")
+    })?;
+
+    let operation = match symbol {
+        "+" => Operation::Add,
+        "*" => Operation::Multiply,
+        _ => return Err(ParseError::at(11, input, symbol, "expected + or *")),
+    };
+
+    let magnitude = match magnitude {
+        "old" => Magnitude::SameAsBefore,
+        _ => Magnitude::Factor(parse::number(
+            11,
+            input,
+            magnitude,
+            "expected a number or old",
+        )?),
+    };
+
+    Ok((operation, magnitude))
+}
+
+fn parse_input(input: &str) -> Result<HashMap<usize, Monkey>, ParseError> {
+    let mut monkeys = HashMap::<usize, Monkey>::new();
+    // Targets are checked once every monkey is known
+    let mut targets = Vec::<&str>::new();
+    let mut lines = input.lines().peekable();
+
+    while lines.peek().is_some() {
+        let expected_id = format!("expected monkey {}", monkeys.len());
+        let id = next_field(input, &mut lines, "Monkey ", "Monkey 0:")?;
+        let id = id.strip_suffix(':').unwrap_or(id);
+        if parse::number::<usize>(11, input, id, &expected_id)? != monkeys.len() {
+            return Err(ParseError::at(11, input, id, &expected_id));
+        }
+
+        let items = next_field(
+            input,
+            &mut lines,
+            "Starting items:",
+            "  Starting items: 79, 98",
+        )?;
+        let items = match items.trim() {
+            "" => vec![],
+            items => items
+                .split(',')
+                .map(|item| parse::number(11, input, item.trim(), "expected a worry level"))
+                .collect::<Result<Vec<usize>, ParseError>>()?,
+        };
+
+        let operation = next_field(
+            input,
+            &mut lines,
+            "Operation: new = old ",
+            "  Operation: new = old * 19",
+        )?;
+        let (operation, magnitude) = capture_operation(input, operation)?;
+
+        let test_value = next_field(
+            input,
+            &mut lines,
+            "Test: divisible by ",
+            "  Test: divisible by 23",
+        )?;
+        let hint = "expected a divisor above 0";
+        let test_value = match parse::number(11, input, test_value, hint)? {
+            0 => return Err(ParseError::at(11, input, test_value, hint)),
+            test_value => test_value,
+        };
+
+        let true_target = next_field(
+            input,
+            &mut lines,
+            "If true: throw to monkey ",
+            "    If true: throw to monkey 2",
+        )?;
+        let false_target = next_field(
+            input,
+            &mut lines,
+            "If false: throw to monkey ",
+            "    If false: throw to monkey 3",
+        )?;
+        targets.extend([true_target, false_target]);
+
+        let monkey = Monkey {
+            items: items.into_iter().collect(),
+            operation,
+            magnitude,
+            test_value,
+            true_target: parse::number(11, input, true_target, "expected a monkey number")?,
+            false_target: parse::number(11, input, false_target, "expected a monkey number")?,
+        };
+        monkeys.insert(monkeys.len(), monkey);
+
+        // Skip the blank line separating input blocks
+        match lines.next() {
+            None | Some("") => {}
+            Some(line) => {
+                let hint = "expected a blank line between monkeys";
+                return Err(ParseError::at(11, input, line, hint));
+            }
+        }
+    }
+
+    let hint = format!(
+        "expected a monkey from 0 to {}",
+        monkeys.len().saturating_sub(1)
+    );
+    for target in targets {
+        if parse::number::<usize>(11, input, target, &hint)? >= monkeys.len() {
+            return Err(ParseError::at(11, input, target, &hint));
+        }
+    }
+
+    Ok(monkeys)
+}
+
+fn solve_part1(input: &HashMap<usize, Monkey>) -> Result<usize, SolveError> {
+    let rounds = 20;
+    let worry_factor = Some(3.0);
+    let monkey_business_factor = 2;
+
+    let mut tracker = MonkeyBusinessTracker::new_from_input(input.clone());
+    let lcm = tracker.get_lcm()?;
+    if tracing!(Level::Debug) {
+        tracker.trace_items();
+    }
+    for round in 1..=rounds {
+        debug!("Round {}", round);
+        for monkey_id in 0..tracker.monkeys.len() {
+            let item_count = tracker.monkeys.get(&monkey_id).unwrap().items.len();
+            tracker.update_activity(monkey_id, item_count);
+            let monkey = tracker.monkeys.get_mut(&monkey_id).unwrap();
+            let updates = monkey.run_turn(worry_factor, lcm)?;
+            tracker.update_items(updates);
+        }
+        if tracing!(Level::Debug) {
+            tracker.trace_items();
+        }
+    }
+    if tracing!(Level::Debug) {
+        tracker.trace_inspections();
+    }
+    Ok(tracker.calculate(monkey_business_factor))
+}
+
+fn solve_part2(input: &HashMap<usize, Monkey>) -> Result<usize, SolveError> {
+    let rounds = 10_000;
+    let worry_factor = None;
+    let monkey_business_factor = 2;
+    let debug_rounds: Vec<i32> = vec![
+        1, 20, 1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000, 10000,
+    ];
+
+    let mut tracker = MonkeyBusinessTracker::new_from_input(input.clone());
+    let lcm = tracker.get_lcm()?;
+    for i in 0..rounds {
+        for monkey_id in 0..tracker.monkeys.len() {
+            let item_count = tracker.monkeys.get(&monkey_id).unwrap().items.len();
+            tracker.update_activity(monkey_id, item_count);
+            let monkey = tracker.monkeys.get_mut(&monkey_id).unwrap();
+            let updates = monkey.run_turn(worry_factor, lcm)?;
+            tracker.update_items(updates);
+        }
+        if tracing!(Level::Debug) && debug_rounds.contains(&(i + 1)) {
+            debug!("Round {}", i + 1);
+            tracker.trace_items();
+            tracker.trace_inspections();
+        }
+    }
+    Ok(tracker.calculate(monkey_business_factor))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = HashMap<usize, Monkey>;
+
+    fn parse(input: &str) -> Result<HashMap<usize, Monkey>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &HashMap<usize, Monkey>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &HashMap<usize, Monkey>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
@@ -0,0 +1,334 @@
+#![allow(non_upper_case_globals)]
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::geom::{Direction, UPoint2};
+use crate::grid::{Grid, Position};
+use crate::parse::{self, ParseError};
+use crate::search::{self, Graph};
+use crate::solution::{Answer, Solution, SolveError, Variant};
+use crate::{debug, trace};
+
+const a_ASCII: usize = 97;
+const z_ASCII: usize = 122;
+const S_ASCII: usize = 83;
+const E_ASCII: usize = 69;
+
+#[derive(Debug, Clone)]
+pub struct TopographicMap {
+    map: Grid<usize>,
+    start: Position,
+    end: Position,
+}
+
+impl TopographicMap {
+    fn new_from_input(input: &str) -> Result<Self, ParseError> {
+        let map = Grid::parse(
+            12,
+            input,
+            "expected a height from a to z, S or E",
+            |c| match c {
+                'a'..='z' | 'S' | 'E' => Some(c as usize),
+                _ => None,
+            },
+        )?;
+        validate_start_and_end(input)?;
+
+        let mut topographic_map = TopographicMap {
+            map,
+            start: Position::new(0, 0),
+            end: Position::new(0, 0),
+        };
+        topographic_map.locate_start_and_end();
+        Ok(topographic_map)
+    }
+
+    #[allow(non_snake_case)]
+    fn locate_start_and_end(&mut self) {
+        for (point, height) in self.map.iter() {
+            if *height == S_ASCII {
+                self.start = point;
+            } else if *height == E_ASCII {
+                self.end = point;
+            }
+        }
+        self.map[self.start] = a_ASCII;
+        self.map[self.end] = z_ASCII;
+    }
+
+    fn is_point_selectable(&self, current: &Position, candidate: &Position) -> bool {
+        let current_height = self.map[*current];
+        let candidate_height = self.map[*candidate];
+
+        if candidate_height > current_height && candidate_height - current_height > 1 {
+            return false;
+        }
+
+        true
+    }
+
+    // The map with the path drawn on it in arrows
+    fn render_path(&self, path: &[Position]) -> String {
+        let mut rendered = String::new();
+        for row in 0..self.map.height() {
+            for col in 0..self.map.width() {
+                let point = Position::new(row, col);
+                if path.contains(&point) {
+                    let index = path.iter().position(|p| *p == point).unwrap();
+                    let current = path[index];
+                    if current == self.start {
+                        rendered.push('S');
+                        continue;
+                    }
+                    if current == self.end {
+                        rendered.push('E');
+                        continue;
+                    }
+
+                    let next = path[index + 1];
+                    let step = UPoint2::from(next).signed() - UPoint2::from(current).signed();
+                    let direction = Direction::from_offset(step).expect("path takes single steps");
+                    rendered.push(direction.arrow());
+                } else {
+                    rendered.push('.');
+                }
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+// implement Display for TopographicMap. Print out each character in the grid:
+impl fmt::Display for TopographicMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = self.start;
+        let end = self.end;
+
+        let cells = self.map.display(|point, &height| match point {
+            point if point == start => 'S',
+            point if point == end => 'E',
+            _ => height as u8 as char,
+        });
+        write!(f, "{}", cells)?;
+
+        writeln!(f, "Start: {:?}", self.start)?;
+        writeln!(f, "End: {:?}", self.end)?;
+        Ok(())
+    }
+}
+
+// Each step climbs at most one higher, but can drop any distance
+impl Graph for TopographicMap {
+    type Node = Position;
+
+    fn neighbours(&self, point: &Position) -> impl Iterator<Item = (Position, usize)> {
+        let point = *point;
+        self.map
+            .neighbours4(point)
+            .filter(move |candidate| self.is_point_selectable(&point, candidate))
+            .map(|candidate| (candidate, 1))
+    }
+}
+
+// Exactly one start S and one end E
+fn validate_start_and_end(input: &str) -> Result<(), ParseError> {
+    for (c, name) in [('S', "a start S"), ('E', "an end E")] {
+        let mut found = input.match_indices(c).map(|(i, _)| &input[i..i + 1]);
+        if found.next().is_none() {
+            let hint = format!("expected {}", name);
+            return Err(ParseError::at(12, input, parse::end(input), hint));
+        }
+        if let Some(token) = found.next() {
+            let hint = format!("expected only one {}", c);
+            return Err(ParseError::at(12, input, token, hint));
+        }
+    }
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<TopographicMap, ParseError> {
+    TopographicMap::new_from_input(input)
+}
+
+fn solve_part1(map: &TopographicMap) -> Result<usize, SolveError> {
+    let path =
+        search::bfs(map, [map.start], |&point| point == map.end).ok_or(SolveError::NoSolution)?;
+    trace!("{}", map);
+    debug!("{}", map.render_path(&path.nodes));
+    Ok(path.steps())
+}
+
+// The original search, Dijkstra without a queue: every step scans all the unvisited points
+// for the closest one, so it takes time quadratic in the size of the map
+fn solve_part1_slow(map: &TopographicMap) -> Result<usize, SolveError> {
+    let mut distances = HashMap::from([(map.start, 0)]);
+    let mut unvisited: HashSet<Position> = map.map.positions().collect();
+    loop {
+        let (current, distance) = unvisited
+            .iter()
+            .filter_map(|point| Some((*point, *distances.get(point)?)))
+            .min_by_key(|&(_, distance)| distance)
+            .ok_or(SolveError::NoSolution)?;
+        if current == map.end {
+            return Ok(distance);
+        }
+        unvisited.remove(&current);
+        for (neighbour, cost) in map.neighbours(&current) {
+            if unvisited.contains(&neighbour) {
+                let known = distances.entry(neighbour).or_insert(usize::MAX);
+                *known = (*known).min(distance + cost);
+            }
+        }
+    }
+}
+
+fn solve_part1_priority_queue(map: &TopographicMap) -> Result<usize, SolveError> {
+    let path = search::dijkstra(map, [map.start], |&point| point == map.end);
+    Ok(path.ok_or(SolveError::NoSolution)?.steps())
+}
+
+// The distance left is at least the number of steps to the end ignoring heights
+fn solve_part1_astar(map: &TopographicMap) -> Result<usize, SolveError> {
+    let end = UPoint2::from(map.end);
+    let path = search::astar(
+        map,
+        [map.start],
+        |&point| point == map.end,
+        |&point| UPoint2::from(point).manhattan(end),
+    );
+    Ok(path.ok_or(SolveError::NoSolution)?.steps())
+}
+
+// Search from every lowest point at once, the first to reach the end is the closest
+fn solve_part2(input: &TopographicMap) -> Result<usize, SolveError> {
+    let starts = input
+        .map
+        .iter()
+        .filter(|(_, &height)| height == a_ASCII)
+        .map(|(point, _)| point);
+    let path = search::bfs(input, starts, |&point| point == input.end);
+    Ok(path.ok_or(SolveError::NoSolution)?.steps())
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const VARIANTS: &'static [Variant<TopographicMap>] = &[
+        Variant {
+            part: 1,
+            name: "slow",
+            solve: |input| solve_part1_slow(input).map(Answer::from),
+        },
+        Variant {
+            part: 1,
+            name: "priority_queue",
+            solve: |input| solve_part1_priority_queue(input).map(Answer::from),
+        },
+        Variant {
+            part: 1,
+            name: "astar",
+            solve: |input| solve_part1_astar(input).map(Answer::from),
+        },
+    ];
+
+    type Input = TopographicMap;
+
+    fn parse(input: &str) -> Result<TopographicMap, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &TopographicMap) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &TopographicMap) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::property;
+
+    fn elevation(c: u8) -> u8 {
+        match c {
+            b'S' => b'a',
+            b'E' => b'z',
+            c => c,
+        }
+    }
+
+    // A plain breadth first search, from one start to the E
+    fn steps(rows: &[&[u8]], start: (usize, usize)) -> Option<usize> {
+        let mut distances = vec![vec![None; rows[0].len()]; rows.len()];
+        distances[start.0][start.1] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some((row, column)) = queue.pop_front() {
+            let distance = distances[row][column]?;
+            if rows[row][column] == b'E' {
+                return Some(distance);
+            }
+            let neighbours = [
+                (row.wrapping_sub(1), column),
+                (row + 1, column),
+                (row, column.wrapping_sub(1)),
+                (row, column + 1),
+            ];
+            for (r, c) in neighbours {
+                if r >= rows.len() || c >= rows[0].len() || distances[r][c].is_some() {
+                    continue;
+                }
+                if elevation(rows[r][c]) <= elevation(rows[row][column]) + 1 {
+                    distances[r][c] = Some(distance + 1);
+                    queue.push_back((r, c));
+                }
+            }
+        }
+        None
+    }
+
+    // The shortest of the searches from every start that reaches the E at all
+    fn fewest_steps(input: &str, start: fn(u8) -> bool) -> Option<Answer> {
+        let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let width = rows.first()?.len();
+        let valid = |c: &u8| c.is_ascii_lowercase() || *c == b'S' || *c == b'E';
+        if rows
+            .iter()
+            .any(|row| row.len() != width || !row.iter().all(valid))
+        {
+            return None;
+        }
+        let count = |wanted| input.bytes().filter(|&c| c == wanted).count();
+        if count(b'S') != 1 || count(b'E') != 1 {
+            return None;
+        }
+
+        let mut starts = vec![];
+        for (row, line) in rows.iter().enumerate() {
+            for (column, &c) in line.iter().enumerate() {
+                if start(c) {
+                    starts.push((row, column));
+                }
+            }
+        }
+        starts
+            .into_iter()
+            .filter_map(|start| steps(&rows, start))
+            .min()
+            .map(Answer::from)
+    }
+
+    #[test]
+    fn test_against_oracle() {
+        property::assert_oracle(2022, 12, 1, |input| fewest_steps(input, |c| c == b'S'));
+        property::assert_oracle(2022, 12, 2, |input| {
+            fewest_steps(input, |c| elevation(c) == b'a')
+        });
+    }
+}
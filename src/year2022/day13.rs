@@ -1,7 +1,8 @@
-use std::{collections::VecDeque, vec};
 use std::cmp::Ordering;
+use std::{collections::VecDeque, vec};
 
-use anyhow::{anyhow, Result};
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Data {
@@ -38,7 +39,7 @@ impl Ord for Data {
                         }
                     }
                 }
-            },
+            }
             (Data::Number(l), Data::List(_)) => {
                 let lhs = Data::List(vec![Data::Number(*l)]);
                 lhs.cmp(rhs)
@@ -58,7 +59,7 @@ impl PartialOrd for Data {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Packet {
+pub struct Packet {
     data: Vec<Data>,
 }
 
@@ -97,56 +98,63 @@ impl PartialOrd for Packet {
 }
 
 impl Packet {
+    // Errors point into the line, the caller moves them to the right line of the input
     #[allow(clippy::while_let_on_iterator)]
-    fn read(line: &str) -> Result<Packet> {
+    fn read(line: &str) -> Result<Packet, ParseError> {
         let mut packet = Packet {
             data: Vec::<Data>::new(),
         };
         let mut stack = VecDeque::<Data>::new();
+        // How many lists are still open
+        let mut depth = 0;
 
         if line.is_empty() {
-            return Err(anyhow!("Empty line"));
+            return Err(ParseError::at(13, line, line, "expected a packet"));
         }
 
         // loop over line, pushing and popping from stack
         // When a square bracket is encountered, push a new Data onto the stack
         // When a number is encountered, append it to the last Data in the stack
         // When a closing brack is encountered, pop the last Data from the stack and append it to the previous Data in the stack
-        let mut iterator = line.chars().peekable();
-        while let Some(c) = iterator.next() {
-            // exit if this line is empty
+        let mut iterator = line.char_indices().peekable();
+        while let Some((i, c)) = iterator.next() {
+            let token = &line[i..i + c.len_utf8()];
             match c {
                 '[' => {
                     let data = Data::List(Vec::<Data>::new());
                     stack.push_back(data);
+                    depth += 1;
                 }
                 '0'..='9' => {
                     // Need to handle multi digit numbers
                     // Read all digits until we hit a comma or closing bracket
-                    let mut chars = Vec::<char>::new();
-                    chars.push(c);
-                    while let Some(b) = iterator.peek() {
+                    let mut end = i + 1;
+                    while let Some(&(j, b)) = iterator.peek() {
                         match b {
                             ',' | ']' => {
                                 break;
                             }
                             '0'..='9' => {
-                                let b = iterator.next();
-                                chars.push(b.unwrap());
+                                iterator.next();
+                                end = j + 1;
+                            }
+                            _ => {
+                                let token = &line[j..j + b.len_utf8()];
+                                let hint = "expected a digit, , or ]";
+                                return Err(ParseError::at(13, line, token, hint));
                             }
-                            _ => return Err(anyhow!("Unexpected character: {}", b)),
                         }
                     }
 
-                    let number = chars.iter().collect::<String>().parse::<usize>().unwrap();
+                    let number = parse::number(13, line, &line[i..end], "expected a number")?;
                     let data = Data::Number(number);
 
                     match stack.back_mut() {
-                        Some(Data::List(list)) => {
+                        Some(Data::List(list)) if depth > 0 => {
                             list.push(data);
                         }
                         _ => {
-                            return Err(anyhow!("Expected a list on the stack"));
+                            return Err(ParseError::at(13, line, &line[i..end], "expected ["));
                         }
                     }
                 }
@@ -154,75 +162,79 @@ impl Packet {
                     continue;
                 }
                 ']' => {
+                    if depth == 0 {
+                        return Err(ParseError::at(13, line, token, "expected no more ]"));
+                    }
+                    depth -= 1;
                     let data = stack.pop_back().unwrap();
                     match stack.back_mut() {
-                        Some(Data::List(list)) => {
+                        Some(Data::List(list)) if depth > 0 => {
                             list.push(data);
                         }
-                        Some(Data::Number(_)) => {
-                            return Err(anyhow!("Expected a list on the stack"));
-                        }
-                        None => {
+                        _ => {
                             stack.push_back(data);
                         }
                     }
                 }
                 _ => {
-                    return Err(anyhow!("Unexpected character: {}", c));
+                    return Err(ParseError::at(
+                        13,
+                        line,
+                        token,
+                        "expected [, ], , or a digit",
+                    ));
                 }
             }
         }
+        if depth > 0 {
+            return Err(ParseError::at(13, line, parse::end(line), "expected ]"));
+        }
         stack.into_iter().for_each(|d| packet.data.push(d));
 
         Ok(packet)
     }
 }
 
-#[aoc_generator(day13)]
-fn input_generator(input: &str) -> Vec<(Packet, Packet)> {
+fn input_generator(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
     // Read the input line by line, parsing each line into a Packet
-    // Then, for every pair of Packets, create a tuple
-    // Then collect into a Vec<(Packet, Packet)>
-    let packets = input
-        .lines()
-        .filter_map(|line| {
-            match Packet::read(line) {
-                Ok(packet) => Some(packet),
-                Err(_) => None, // TODO: Check if this is a panic-worthy error instead of assuming no packet
-            }
-        })
-        .collect::<Vec<Packet>>();
-
-    packets
-        .chunks(2)
-        .map(|chunk| {
-            if chunk.len() != 2 {
-                panic!("Expected a chunk of 2 packets, got {:?}", chunk.len());
+    // Pairs of packets are separated by a blank line
+    let mut pairs = Vec::<(Packet, Packet)>::new();
+    let mut lines = input.lines().enumerate().peekable();
+    let read = |(i, line): (usize, &str)| Packet::read(line).map_err(|err| err.on_line(i + 1));
+
+    while let Some(first) = lines.next() {
+        let second = lines.next().ok_or_else(|| {
+            ParseError::at(13, input, parse::end(input), "expected a second packet")
+        })?;
+        pairs.push((read(first)?, read(second)?));
+
+        match lines.next() {
+            None | Some((_, "")) => {}
+            Some((_, line)) => {
+                let hint = "expected a blank line between pairs of packets";
+                return Err(ParseError::at(13, input, line, hint));
             }
-            let lhs = chunk[0].clone();
-            let rhs = chunk[1].clone();
-            (lhs, rhs)
-        })
-        .collect()
+        }
+    }
+    Ok(pairs)
 }
 
-#[aoc(day13, part1)]
-fn solve_part1(packets: &[(Packet, Packet)]) -> usize {
+fn solve_part1(packets: &[(Packet, Packet)]) -> Result<usize, SolveError> {
     let mut count = 0;
     for (i, (lhs, rhs)) in packets.iter().enumerate() {
         if lhs < rhs {
             count += i + 1;
         }
     }
-    count
+    Ok(count)
 }
 
-#[aoc(day13, part2)]
-fn solve_part2(packets: &[(Packet, Packet)]) -> usize {
-    let mut packets = packets.iter()
+fn solve_part2(packets: &[(Packet, Packet)]) -> Result<usize, SolveError> {
+    let mut packets = packets
+        .iter()
         .flat_map(|(p1, p2)| vec![p1, p2])
         .collect::<Vec<&Packet>>();
-    let dividers = vec![
+    let dividers = [
         Packet::read("[[2]]").unwrap(),
         Packet::read("[[6]]").unwrap(),
     ];
@@ -235,7 +247,27 @@ fn solve_part2(packets: &[(Packet, Packet)]) -> usize {
             key *= i + 1;
         }
     }
-    key
+    Ok(key)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Vec<(Packet, Packet)>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Vec<(Packet, Packet)>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
@@ -243,6 +275,7 @@ mod tests {
     use std::vec;
 
     use super::*;
+    use crate::property;
 
     #[test]
     fn test_read_packet() {
@@ -348,7 +381,7 @@ mod tests {
                      [1]\n\
                      \n\
                      [2]\n\
-                     [2]\n\\";
+                     [2]\n";
         let expected = vec![
             (
                 Packet {
@@ -367,7 +400,7 @@ mod tests {
                 },
             ),
         ];
-        let result = input_generator(input);
+        let result = input_generator(input).unwrap();
         assert_eq!(result, expected, "Packets should match");
     }
 
@@ -544,4 +577,110 @@ mod tests {
             assert_eq!(result, case.expected, "{}", case.description);
         }
     }
+
+    // A packet as nested lists, compared by following the puzzle's rules recursively
+    #[derive(Debug, Clone)]
+    enum Value {
+        Number(u32),
+        List(Vec<Value>),
+    }
+
+    // The value at the start of `text`, and what is left after it
+    fn value(text: &str) -> Option<(Value, &str)> {
+        if let Some(mut rest) = text.strip_prefix('[') {
+            let mut list = vec![];
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Some((Value::List(list), rest));
+            }
+            loop {
+                let (item, after) = value(rest)?;
+                list.push(item);
+                match after.as_bytes().first()? {
+                    b',' => rest = &after[1..],
+                    b']' => return Some((Value::List(list), &after[1..])),
+                    _ => return None,
+                }
+            }
+        }
+        let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let number = text[..digits].parse().ok()?;
+        Some((Value::Number(number), &text[digits..]))
+    }
+
+    fn packet(line: &str) -> Option<Value> {
+        match value(line)? {
+            (list @ Value::List(_), "") => Some(list),
+            _ => None,
+        }
+    }
+
+    fn compare(left: &Value, right: &Value) -> Ordering {
+        match (left, right) {
+            (Value::Number(left), Value::Number(right)) => left.cmp(right),
+            (Value::Number(number), right) => {
+                compare(&Value::List(vec![Value::Number(*number)]), right)
+            }
+            (left, Value::Number(number)) => {
+                compare(left, &Value::List(vec![Value::Number(*number)]))
+            }
+            (Value::List(left), Value::List(right)) => {
+                for (left, right) in left.iter().zip(right) {
+                    match compare(left, right) {
+                        Ordering::Equal => continue,
+                        order => return order,
+                    }
+                }
+                left.len().cmp(&right.len())
+            }
+        }
+    }
+
+    fn pairs(input: &str) -> Option<Vec<(Value, Value)>> {
+        input
+            .split("\n\n")
+            .map(|pair| match pair.lines().collect::<Vec<&str>>()[..] {
+                [left, right] => Some((packet(left)?, packet(right)?)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ordered_pairs(input: &str) -> Option<Answer> {
+        let pairs = pairs(input)?;
+        let ordered = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right))| compare(left, right) == Ordering::Less)
+            .map(|(i, _)| i + 1);
+        Some(ordered.sum::<usize>().into())
+    }
+
+    // Each divider's index is one more than the number of packets before it, no sorting needed
+    fn decoder_key(input: &str) -> Option<Answer> {
+        let packets: Vec<Value> = pairs(input)?
+            .into_iter()
+            .flat_map(|(left, right)| [left, right])
+            .collect();
+        let dividers = [packet("[[2]]")?, packet("[[6]]")?];
+        let mut key = 1;
+        for (i, divider) in dividers.iter().enumerate() {
+            let mut before = i;
+            for packet in &packets {
+                match compare(packet, divider) {
+                    Ordering::Less => before += 1,
+                    // A packet that is a divider makes the key ambiguous
+                    Ordering::Equal => return None,
+                    Ordering::Greater => {}
+                }
+            }
+            key *= before + 1;
+        }
+        Some(key.into())
+    }
+
+    #[test]
+    fn test_against_oracle() {
+        property::assert_oracle(2022, 13, 1, ordered_pairs);
+        property::assert_oracle(2022, 13, 2, decoder_key);
+    }
 }
@@ -0,0 +1,141 @@
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution, SolveError};
+
+#[derive(Clone, Copy)]
+enum Play {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+// The second column is our move in part 1, and the outcome we need in part 2
+#[derive(Clone, Copy)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+pub struct Round {
+    their_move: Play,
+    second: Column,
+}
+
+fn parse_input(input: &str) -> Result<Vec<Round>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let hint = "expected a round like \"A Y\"";
+            let (their_move, second) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::at(2, input, line, hint))?;
+            let their_move = match their_move {
+                "A" => Play::Rock,
+                "B" => Play::Paper,
+                "C" => Play::Scissors,
+                _ => return Err(ParseError::at(2, input, their_move, "expected A, B or C")),
+            };
+            let second = match second {
+                "X" => Column::X,
+                "Y" => Column::Y,
+                "Z" => Column::Z,
+                _ => return Err(ParseError::at(2, input, second, "expected X, Y or Z")),
+            };
+            Ok(Round { their_move, second })
+        })
+        .collect()
+}
+
+fn solve_part1(input: &[Round]) -> Result<usize, SolveError> {
+    let mut score: usize = 0;
+    for round in input {
+        let our_move = parse_play(round.second);
+        score += derive_score(our_move, round.their_move);
+    }
+    Ok(score)
+}
+
+fn solve_part2(input: &[Round]) -> Result<usize, SolveError> {
+    let mut score: usize = 0;
+    for round in input {
+        let outcome = parse_plan(round.second);
+        let our_move = determine_move(round.their_move, outcome);
+        score += derive_score(our_move, round.their_move);
+    }
+    Ok(score)
+}
+
+fn parse_play(play: Column) -> Play {
+    match play {
+        Column::X => Play::Rock,
+        Column::Y => Play::Paper,
+        Column::Z => Play::Scissors,
+    }
+}
+
+#[allow(clippy::identity_op)]
+fn derive_score(our_move: Play, their_move: Play) -> usize {
+    match (our_move, their_move) {
+        (Play::Rock, Play::Rock) => our_move as usize + 3,
+        (Play::Rock, Play::Paper) => our_move as usize + 0,
+        (Play::Rock, Play::Scissors) => our_move as usize + 6,
+
+        (Play::Paper, Play::Rock) => our_move as usize + 6,
+        (Play::Paper, Play::Paper) => our_move as usize + 3,
+        (Play::Paper, Play::Scissors) => our_move as usize + 0,
+
+        (Play::Scissors, Play::Rock) => our_move as usize + 0,
+        (Play::Scissors, Play::Paper) => our_move as usize + 6,
+        (Play::Scissors, Play::Scissors) => our_move as usize + 3,
+    }
+}
+
+fn parse_plan(plan: Column) -> Outcome {
+    match plan {
+        Column::X => Outcome::Lose,
+        Column::Y => Outcome::Draw,
+        Column::Z => Outcome::Win,
+    }
+}
+
+fn determine_move(their_move: Play, outcome: Outcome) -> Play {
+    match (their_move, outcome) {
+        (Play::Rock, Outcome::Lose) => Play::Scissors,
+        (Play::Rock, Outcome::Draw) => Play::Rock,
+        (Play::Rock, Outcome::Win) => Play::Paper,
+
+        (Play::Paper, Outcome::Lose) => Play::Rock,
+        (Play::Paper, Outcome::Draw) => Play::Paper,
+        (Play::Paper, Outcome::Win) => Play::Scissors,
+
+        (Play::Scissors, Outcome::Lose) => Play::Paper,
+        (Play::Scissors, Outcome::Draw) => Play::Scissors,
+        (Play::Scissors, Outcome::Win) => Play::Rock,
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<Round>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Vec<Round>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution, SolveError};
+use crate::warn;
+use crate::{ASCII_LOWERCASE, ASCII_UPPERCASE};
+
+type Item = char;
+type Priority = usize;
+
+#[derive(Debug)]
+pub struct Backpack {
+    compartment1: Vec<Item>,
+    compartment2: Vec<Item>,
+}
+
+impl Backpack {
+    fn all(&self) -> Vec<Item> {
+        self.compartment1
+            .iter()
+            .cloned()
+            .chain(self.compartment2.iter().cloned())
+            .collect()
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Backpack>, ParseError> {
+    let mut backpacks = Vec::<Backpack>::new();
+    for line in input.split('\n') {
+        if let Some((i, item)) = line
+            .char_indices()
+            .find(|(_, item)| !item.is_ascii_alphabetic())
+        {
+            let item = &line[i..i + item.len_utf8()];
+            return Err(ParseError::at(
+                3,
+                input,
+                item,
+                "expected an item from a to z or A to Z",
+            ));
+        }
+        if line.is_empty() || line.len() % 2 != 0 {
+            return Err(ParseError::at(
+                3,
+                input,
+                line,
+                "expected an even number of items, to split between two compartments",
+            ));
+        }
+        let divider = line.len() / 2;
+
+        let backpack = Backpack {
+            compartment1: line[..divider].chars().collect(),
+            compartment2: line[divider..].chars().collect(),
+        };
+
+        backpacks.push(backpack);
+    }
+    Ok(backpacks)
+}
+
+fn solve_part1(input: &[Backpack]) -> Result<Priority, SolveError> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, backpack)| {
+            let misplaced = find_misplaced_items(backpack).ok_or_else(|| {
+                SolveError::invariant(format!(
+                    "expected exactly one item in both compartments of backpack {}",
+                    i + 1
+                ))
+            })?;
+            check_priority(misplaced[0])
+        })
+        .sum()
+}
+
+fn solve_part2(input: &[Backpack]) -> Result<Priority, SolveError> {
+    if !input.len().is_multiple_of(3) {
+        return Err(SolveError::invariant(format!(
+            "expected groups of 3 backpacks, found {} backpacks",
+            input.len()
+        )));
+    }
+
+    let mut priorities = 0;
+    for i in 0..input.len() / 3 {
+        let j = i * 3;
+        let backpack1 = &input[j];
+        let backpack2 = &input[j + 1];
+        let backpack3 = &input[j + 2];
+
+        let badge = find_badge_item(backpack1, backpack2, backpack3).ok_or_else(|| {
+            SolveError::invariant(format!("expected exactly one badge in group {}", i + 1))
+        })?;
+        priorities += check_priority(badge)?;
+    }
+    Ok(priorities)
+}
+
+fn check_priority(item: char) -> Result<Priority, SolveError> {
+    if ASCII_LOWERCASE.contains(&item) {
+        Ok(item as usize - 96)
+    } else if ASCII_UPPERCASE.contains(&item) {
+        Ok(item as usize - 38)
+    } else {
+        Err(SolveError::invariant(format!("{:?} is not an item", item)))
+    }
+}
+
+fn find_misplaced_items(backpack: &Backpack) -> Option<Vec<Item>> {
+    let compartment1 = HashSet::<Item>::from_iter(backpack.compartment1.clone());
+    let compartment2 = HashSet::<Item>::from_iter(backpack.compartment2.clone());
+
+    let intersection: Vec<Item> = compartment1.intersection(&compartment2).cloned().collect();
+
+    match intersection.len() {
+        0 => None,
+        1 => Some(intersection),
+        _ => {
+            warn!("More than one misplaced item found {:?}", intersection);
+            None
+        }
+    }
+}
+
+fn find_badge_item(
+    backpack1: &Backpack,
+    backpack2: &Backpack,
+    backpack3: &Backpack,
+) -> Option<Item> {
+    let items1 = HashSet::<Item>::from_iter(backpack1.all());
+    let items2 = HashSet::<Item>::from_iter(backpack2.all());
+    let items3 = HashSet::<Item>::from_iter(backpack3.all());
+
+    let intersection = HashSet::<Item>::from_iter(items1.intersection(&items2).cloned());
+    let intersection: Vec<Item> = intersection.intersection(&items3).cloned().collect();
+
+    match intersection.len() {
+        0 => None,
+        1 => Some(intersection[0]),
+        _ => {
+            warn!("More than one badge found {:?}", intersection);
+            None
+        }
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Backpack>;
+
+    fn parse(input: &str) -> Result<Vec<Backpack>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<Backpack>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Vec<Backpack>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
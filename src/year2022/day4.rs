@@ -0,0 +1,141 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
+
+#[derive(Debug)]
+pub struct SectionID {
+    start: usize,
+    end: usize,
+}
+
+fn parse_input(input: &str) -> Result<Vec<(SectionID, SectionID)>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let split: Vec<&str> = line.split(',').collect();
+            let (id1, id2) = match split.len() {
+                2 => (parse_id(input, split[0])?, parse_id(input, split[1])?),
+                _ => {
+                    return Err(ParseError::at(
+                        4,
+                        input,
+                        line,
+                        "expected a pair of section ranges like 2-4,6-8",
+                    ))
+                }
+            };
+            Ok((id1, id2))
+        })
+        .collect()
+}
+
+fn solve_part1(input: &[(SectionID, SectionID)]) -> Result<usize, SolveError> {
+    Ok(input
+        .iter()
+        .map(|ids| check_complete_overlap(&ids.0, &ids.1))
+        .map(|yes| yes as usize)
+        .sum())
+}
+
+fn solve_part2(input: &[(SectionID, SectionID)]) -> Result<usize, SolveError> {
+    Ok(input
+        .iter()
+        .map(|ids| check_partial_overlap(&ids.0, &ids.1))
+        .map(|yes| yes as usize)
+        .sum())
+}
+
+// `id` is a slice of `input`, so errors can point at it
+fn parse_id(input: &str, id: &str) -> Result<SectionID, ParseError> {
+    let hint = "expected a section range like 2-4";
+    let split: Vec<&str> = id.split('-').collect();
+    let (start, end) = match split.len() {
+        2 => (
+            parse::number::<usize>(4, input, split[0], hint)?,
+            parse::number::<usize>(4, input, split[1], hint)?,
+        ),
+        _ => return Err(ParseError::at(4, input, id, hint)),
+    };
+
+    Ok(SectionID { start, end })
+}
+
+// Either range may contain the other
+fn check_complete_overlap(id1: &SectionID, id2: &SectionID) -> bool {
+    (id1.start <= id2.start && id1.end >= id2.end) || (id2.start <= id1.start && id2.end >= id1.end)
+}
+
+fn check_partial_overlap(id1: &SectionID, id2: &SectionID) -> bool {
+    id1.start <= id2.end && id2.start <= id1.end
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<(SectionID, SectionID)>;
+
+    fn parse(input: &str) -> Result<Vec<(SectionID, SectionID)>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<(SectionID, SectionID)>) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Vec<(SectionID, SectionID)>) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_complete_overlap() {
+        struct TestCase {
+            description: &'static str,
+            id1: SectionID,
+            id2: SectionID,
+            expected: bool,
+        }
+        let id = |start, end| SectionID { start, end };
+        let cases = vec![
+            TestCase {
+                description: "first contains second",
+                id1: id(2, 8),
+                id2: id(3, 7),
+                expected: true,
+            },
+            TestCase {
+                description: "second contains first",
+                id1: id(6, 6),
+                id2: id(4, 6),
+                expected: true,
+            },
+            TestCase {
+                description: "same range",
+                id1: id(3, 5),
+                id2: id(3, 5),
+                expected: true,
+            },
+            TestCase {
+                description: "partial overlap",
+                id1: id(2, 6),
+                id2: id(4, 8),
+                expected: false,
+            },
+            TestCase {
+                description: "disjoint",
+                id1: id(2, 4),
+                id2: id(6, 8),
+                expected: false,
+            },
+        ];
+        for case in cases {
+            let overlap = check_complete_overlap(&case.id1, &case.id2);
+            assert_eq!(overlap, case.expected, "{}", case.description);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use regex::Regex;
+
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution, SolveError};
+use crate::ASCII_UPPERCASE;
+
+type Stack = Vec<char>;
+
+#[derive(Debug, Clone)]
+pub struct Harbor {
+    size: usize,
+    stacks: Vec<Stack>,
+}
+
+impl Harbor {
+    pub fn new(size: usize) -> Self {
+        Harbor {
+            size,
+            stacks: vec![Stack::default(); size], // n empty Stacks
+        }
+    }
+
+    // Errors are reported as if the line were the whole input, the caller knows which line it is
+    pub fn load_from_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let expected_length = (self.size * 4) - 1;
+        if line.len() != expected_length || !line.is_ascii() {
+            return Err(ParseError::at(
+                5,
+                line,
+                line,
+                format!(
+                    "expected {} characters of crates like \"[A] [B]\"",
+                    expected_length
+                ),
+            ));
+        }
+        let chars: Vec<char> = line.chars().collect();
+
+        for i in 0..self.size {
+            let position = (i * 4) + 1;
+            let contents = chars[position];
+            if ASCII_UPPERCASE.contains(&contents) {
+                self.stacks[i].push(contents)
+            } else if contents != ' ' {
+                return Err(ParseError::at(
+                    5,
+                    line,
+                    &line[position..position + 1],
+                    "expected a crate from A to Z, or a space",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // a Hanoi Move moves items one at a time from the source to destination stack
+    pub fn perform_hanoi_move(&mut self, instruction: &Instruction) -> Result<(), SolveError> {
+        for _ in 0..instruction.quantity {
+            let content = self.pop(instruction)?;
+            self.stacks[instruction.destination].push(content);
+        }
+        Ok(())
+    }
+
+    // a Lift and Shift preserves the original ordering of items while moving them from the source to desination stack
+    pub fn perform_lift_and_shift(&mut self, instruction: &Instruction) -> Result<(), SolveError> {
+        let mut lifted = Stack::default();
+        for _ in 0..instruction.quantity {
+            let content = self.pop(instruction)?;
+            lifted.push(content);
+        }
+
+        while let Some(content) = lifted.pop() {
+            self.stacks[instruction.destination].push(content);
+        }
+        Ok(())
+    }
+
+    fn pop(&mut self, instruction: &Instruction) -> Result<char, SolveError> {
+        self.stacks[instruction.source].pop().ok_or_else(|| {
+            SolveError::invariant(format!(
+                "expected a crate on stack {} to move to stack {}",
+                instruction.source + 1,
+                instruction.destination + 1
+            ))
+        })
+    }
+
+    // The crate on top of each stack
+    fn tops(&self) -> Result<String, SolveError> {
+        self.stacks
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| {
+                stack.last().copied().ok_or_else(|| {
+                    SolveError::invariant(format!("expected a crate on stack {} at the end", i + 1))
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    quantity: usize,
+    source: usize,
+    destination: usize,
+}
+
+fn parse_input(input: &str) -> Result<(Harbor, Vec<Instruction>), ParseError> {
+    let split: Vec<&str> = input.split("\n\n").collect();
+    let (harbor, instructions) = match split.len() {
+        2 => {
+            let harbor = parse_harbor(input, split[0])?;
+            let instructions = parse_instructions(input, split[1], harbor.size)?;
+            (harbor, instructions)
+        }
+        _ => {
+            return Err(ParseError::at(
+                5,
+                input,
+                parse::end(input),
+                "expected the stacks and the moves to be separated by one blank line",
+            ))
+        }
+    };
+    Ok((harbor, instructions))
+}
+
+// `section` is the slice of `input` with the drawing of the stacks
+fn parse_harbor(input: &str, section: &str) -> Result<Harbor, ParseError> {
+    let hint = "expected a line of stack numbers like \" 1   2   3 \"";
+    let lines: Vec<&str> = section.lines().collect();
+    let Some(&stack_ids) = lines.last() else {
+        return Err(ParseError::at(5, input, section, hint));
+    };
+    let num_stacks = match stack_ids.split_whitespace().last() {
+        Some(id) => parse::number::<usize>(5, input, id, hint)?,
+        None => return Err(ParseError::at(5, input, stack_ids, hint)),
+    };
+    // Every stack takes four characters of the line, so a bigger number can't be the last.
+    // The line may have lost its trailing space.
+    if num_stacks == 0 || num_stacks > (stack_ids.len() + 2) / 4 {
+        return Err(ParseError::at(5, input, stack_ids, hint));
+    }
+
+    let mut harbor = Harbor::new(num_stacks);
+    for (i, line) in lines[..lines.len() - 1].iter().enumerate().rev() {
+        harbor
+            .load_from_line(line)
+            .map_err(|err| err.on_line(i + 1))?;
+    }
+    Ok(harbor)
+}
+
+// `section` is the slice of `input` with the moves
+fn parse_instructions(
+    input: &str,
+    section: &str,
+    num_stacks: usize,
+) -> Result<Vec<Instruction>, ParseError> {
+    let expected_format = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    let stack_hint = format!("expected a stack from 1 to {}", num_stacks);
+    section
+        .lines()
+        .map(|line| {
+            let captures = expected_format.captures(line).ok_or_else(|| {
+                ParseError::at(
+                    5,
+                    input,
+                    line,
+                    "expected a move like \"move 1 from 2 to 3\"",
+                )
+            })?;
+            let stack = |index: usize| {
+                let id = captures.get(index).unwrap().as_str();
+                match parse::number::<usize>(5, input, id, &stack_hint)? {
+                    stack if (1..=num_stacks).contains(&stack) => Ok(stack - 1),
+                    _ => Err(ParseError::at(5, input, id, &stack_hint)),
+                }
+            };
+
+            Ok(Instruction {
+                quantity: parse::number(
+                    5,
+                    input,
+                    captures.get(1).unwrap().as_str(),
+                    "expected a number of crates",
+                )?,
+                source: stack(2)?,
+                destination: stack(3)?,
+            })
+        })
+        .collect()
+}
+
+fn solve_part1(input: &(Harbor, Vec<Instruction>)) -> Result<String, SolveError> {
+    let (harbor, instructions) = input;
+    let mut harbor = harbor.clone(); // Cargo AOC only passes input as immutable, so we need to make a clone to work with
+
+    for instruction in instructions.iter() {
+        harbor.perform_hanoi_move(instruction)?;
+    }
+
+    harbor.tops()
+}
+
+fn solve_part2(input: &(Harbor, Vec<Instruction>)) -> Result<String, SolveError> {
+    let (harbor, instructions) = input;
+    let mut harbor = harbor.clone(); // Cargo AOC only passes input as immutable, so we need to make a clone to work with
+
+    for instruction in instructions.iter() {
+        harbor.perform_lift_and_shift(instruction)?;
+    }
+
+    harbor.tops()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Input = (Harbor, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<(Harbor, Vec<Instruction>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &(Harbor, Vec<Instruction>)) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &(Harbor, Vec<Instruction>)) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
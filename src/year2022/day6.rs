@@ -0,0 +1,125 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution, SolveError};
+use crate::trace;
+
+#[derive(Clone, Copy)]
+enum Marker {
+    PacketStart = 4,
+    MessageStart = 14,
+}
+
+struct Identifier {
+    class: Marker,
+    buffer: VecDeque<char>,
+}
+
+impl Identifier {
+    fn new(class: Marker) -> Identifier {
+        Identifier {
+            class,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, item: char) {
+        self.buffer.push_back(item);
+        if self.buffer.len() > self.class as usize {
+            self.buffer.pop_front();
+        }
+    }
+
+    fn check_for_start_of_packet(&self) -> bool {
+        trace!("checking buffer {:?}", self.buffer);
+        let mut seen: HashSet<char> = HashSet::new();
+        if self.buffer.len() != self.class as usize {
+            return false;
+        }
+        for c in self.buffer.iter() {
+            if seen.contains(c) {
+                return false;
+            } else {
+                seen.insert(*c);
+            }
+        }
+        true
+    }
+}
+
+fn find_marker(input: &str, class: Marker) -> Option<usize> {
+    let mut id = Identifier::new(class);
+    for (i, c) in input.chars().enumerate() {
+        id.push(c);
+        if id.check_for_start_of_packet() {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+fn solve_part1(input: &str) -> Result<usize, SolveError> {
+    let marker = find_marker(input, Marker::PacketStart).ok_or(SolveError::NoSolution)?;
+    Ok(marker + 1)
+}
+
+fn solve_part2(input: &str) -> Result<usize, SolveError> {
+    let marker = find_marker(input, Marker::MessageStart).ok_or(SolveError::NoSolution)?;
+    Ok(marker + 1)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        match input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            Some((i, c)) => Err(ParseError::at(
+                6,
+                input,
+                &input[i..i + c.len_utf8()],
+                "expected a single line of letters from a to z",
+            )),
+            None => Ok(input.to_string()),
+        }
+    }
+
+    fn part1(input: &String) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &String) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    // One past the end of the first `length` letters in a row that are all different,
+    // comparing every pair of letters in every window
+    fn first_marker(input: &str, length: usize) -> Option<Answer> {
+        let letters: Vec<char> = input.chars().collect();
+        if !letters.iter().all(char::is_ascii_lowercase) {
+            return None;
+        }
+        (length..=letters.len())
+            .find(|&end| {
+                let window = &letters[end - length..end];
+                (0..length).all(|i| (i + 1..length).all(|j| window[i] != window[j]))
+            })
+            .map(Answer::from)
+    }
+
+    #[test]
+    fn test_find_marker_against_oracle() {
+        property::assert_oracle(2022, 6, 1, |input| first_marker(input, 4));
+        property::assert_oracle(2022, 6, 2, |input| first_marker(input, 14));
+    }
+}
@@ -0,0 +1,415 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use itertools::Either;
+
+use crate::grid::{Grid, Position};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution, SolveError, Variant};
+use crate::ASCII_DIGITS;
+use crate::{debug, trace};
+
+#[derive(Clone, Copy)]
+struct Tree {
+    height: usize,
+    visible_from_outside: bool,
+}
+
+#[derive(Clone)]
+pub struct TreeFarm {
+    plots: Grid<Tree>,
+}
+
+fn parse_input(input: &str) -> Result<TreeFarm, ParseError> {
+    let plots = Grid::parse(8, input, "expected a tree height from 0 to 9", parse_height)?;
+    Ok(TreeFarm { plots })
+}
+
+fn solve_part1(input: &TreeFarm) -> Result<usize, SolveError> {
+    let mut input: TreeFarm = input.clone();
+    debug!("{}", input);
+    for row_index in 0..=input.last_row() {
+        input.visible_trees_in_row(row_index, true);
+        input.visible_trees_in_row(row_index, false);
+    }
+    for column_index in 0..input.last_column() {
+        input.visible_trees_in_col(column_index, true);
+        input.visible_trees_in_col(column_index, false);
+    }
+
+    debug!("{:?}", input);
+    Ok(input.count_visible_trees())
+}
+
+fn solve_part2(input: &TreeFarm) -> Result<usize, SolveError> {
+    debug!("{}", input);
+    let mut high_score = 0;
+    for location in input.plots.positions() {
+        let score = input.base_visibility_score(location);
+        if high_score < score {
+            high_score = score;
+        }
+    }
+
+    Ok(high_score)
+}
+
+// This is actually 10x slower 😅 🤔
+fn solve_part2_2(input: &TreeFarm) -> Result<usize, SolveError> {
+    debug!("{}", input);
+    let mut high_score = 0;
+    for location in input.plots.positions() {
+        let score = input.base_visibility_score_2(location);
+        if high_score < score {
+            high_score = score;
+        }
+    }
+
+    Ok(high_score)
+}
+
+impl fmt::Display for TreeFarm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.plots.display(|_, tree| tree.height))
+    }
+}
+
+// Debug displays the heights of visible trees, and '-' for obscured ones.
+impl fmt::Debug for TreeFarm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let visible = |_, tree: &Tree| match tree.visible_from_outside {
+            true => char::from(b'0' + tree.height as u8),
+            false => '-',
+        };
+        write!(f, "{}", self.plots.display(visible))
+    }
+}
+
+impl TreeFarm {
+    // The index of the bottom row
+    fn last_row(&self) -> usize {
+        self.plots.height() - 1
+    }
+
+    // The index of the rightmost column
+    fn last_column(&self) -> usize {
+        self.plots.width() - 1
+    }
+
+    pub fn count_visible_trees(&self) -> usize {
+        let mut count = 0;
+        for (_, tree) in self.plots.iter() {
+            if tree.visible_from_outside {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn visible_trees_in_row(&mut self, row_index: usize, reversed: bool) {
+        let mut highest_seen = 0;
+        let range = 0..=self.last_column();
+        let counting_order = get_counting_order(range, reversed);
+
+        for (i, column_index) in counting_order.enumerate() {
+            let location = Position::new(row_index, column_index);
+            let tree_height = self.plots[location].height;
+            if i == 0 || tree_height > highest_seen {
+                highest_seen = tree_height;
+                self.plots[location].visible_from_outside = true;
+            }
+
+            // In this scenario 9 is the highest possible tree, so we can short circuit here
+            if highest_seen == 9 {
+                break;
+            }
+        }
+    }
+
+    pub fn visible_trees_in_col(&mut self, column_index: usize, reversed: bool) {
+        let mut highest_seen = 0;
+        let range = 0..=self.last_row();
+        let counting_order = get_counting_order(range, reversed);
+
+        for (i, row_index) in counting_order.enumerate() {
+            let location = Position::new(row_index, column_index);
+            let tree_height = self.plots[location].height;
+            if i == 0 || tree_height > highest_seen {
+                highest_seen = tree_height;
+                self.plots[location].visible_from_outside = true;
+            }
+
+            // In this scenario 9 is the highest possible tree, so we can short circuit here
+            if highest_seen == 9 {
+                break;
+            }
+        }
+    }
+
+    pub fn base_visibility_score(&self, location: Position) -> usize {
+        let base_row = location.row;
+        let base_column = location.column;
+        let base_height = self.plots[location].height;
+        let mut score = 1; // Start at 1, we will *= this in the loops
+
+        // If the base location is on the edge of the forest, the score is 0
+        if base_row == 0
+            || base_row == self.last_row()
+            || base_column == 0
+            || base_column == self.last_column()
+        {
+            return 0;
+        }
+
+        // Iterate from the spaces adjacent to the base to the edge
+
+        // Score Up (Base.Row -1 -> Row 0)
+        let range = (0..=(base_row - 1)).rev();
+        for (i, row_index) in range.enumerate() {
+            if self.plots[Position::new(row_index, base_column)].height >= base_height
+                || row_index == 0
+            {
+                score *= i + 1;
+                break;
+            }
+        }
+
+        // Score Down (Base.Row +1 -> Max Row)
+        let range = (base_row + 1)..=self.last_row();
+        for (i, row_index) in range.enumerate() {
+            if self.plots[Position::new(row_index, base_column)].height >= base_height
+                || row_index == self.last_row()
+            {
+                score *= i + 1;
+                break;
+            }
+        }
+
+        // Score Left (Base -> Row 0)
+        let range = (0..=(base_column - 1)).rev();
+        for (i, column_index) in range.enumerate() {
+            if self.plots[Position::new(base_row, column_index)].height >= base_height
+                || column_index == 0
+            {
+                score *= i + 1;
+                break;
+            }
+        }
+
+        // Score Right (Base -> Max Column)
+        let range = (base_column + 1)..=self.last_column();
+        for (i, column_index) in range.enumerate() {
+            if self.plots[Position::new(base_row, column_index)].height >= base_height
+                || column_index == self.last_column()
+            {
+                score *= i + 1;
+                break;
+            }
+        }
+
+        trace!("location {:?} score {}", location, score);
+        score
+    }
+
+    #[allow(clippy::comparison_chain)]
+    pub fn base_visibility_score_2(&self, location: Position) -> usize {
+        let base_row = location.row;
+        let base_column = location.column;
+        let base_height = self.plots[location].height;
+        let mut score = 1; // Start at 1, we will *= this in the loops
+
+        // If the base location is on the edge of the forest, the score is 0
+        if base_row == 0
+            || base_row == self.last_row()
+            || base_column == 0
+            || base_column == self.last_column()
+        {
+            return 0;
+        }
+
+        // Score Vertical
+        let mut visibility_distance = base_row;
+        let range = 0..=self.last_row();
+        for row_index in range {
+            trace!("row index {row_index}");
+            if row_index < base_row {
+                if self.plots[Position::new(row_index, base_column)].height < base_height {
+                    continue;
+                }
+                visibility_distance = base_row - row_index;
+                trace!("Found blocker at {row_index}. New distance {visibility_distance}");
+            } else if row_index == base_row {
+                trace!("reached base, score {} *= {}", score, visibility_distance);
+                // Reset tracker
+                score *= visibility_distance;
+                visibility_distance = self.last_row() - base_row;
+                trace!("reset distance {visibility_distance}");
+            } else if row_index > base_row && row_index < self.last_row() {
+                if self.plots[Position::new(row_index, base_column)].height < base_height {
+                    continue;
+                }
+                visibility_distance = row_index - base_row;
+                trace!("Found blocker at {row_index}, {score} *= {visibility_distance}");
+                score *= visibility_distance;
+                break;
+            } else if row_index == self.last_row() {
+                trace!("reached limit, {score} *= {visibility_distance}");
+                score *= visibility_distance;
+            } else {
+                continue;
+            }
+        }
+
+        // Score Horizontal
+        let mut visibility_distance = base_column;
+        let range = 0..=self.last_column();
+        for column_index in range {
+            trace!("column index {column_index}");
+            if column_index < base_column {
+                if self.plots[Position::new(base_row, column_index)].height < base_height {
+                    continue;
+                }
+                visibility_distance = base_column - column_index;
+                trace!("Found blocker at {column_index}. New distance {visibility_distance}");
+            } else if column_index == base_column {
+                trace!("reached base, score {} *= {}", score, visibility_distance);
+                // Reset tracker
+                score *= visibility_distance;
+                visibility_distance = self.last_column() - base_column;
+                trace!("reset distance {visibility_distance}");
+            } else if column_index > base_column && column_index < self.last_column() {
+                if self.plots[Position::new(base_row, column_index)].height < base_height {
+                    continue;
+                }
+                visibility_distance = column_index - base_column;
+                trace!("Found blocker at {column_index}, {score} *= {visibility_distance}");
+                score *= visibility_distance;
+                break;
+            } else if column_index == self.last_column() {
+                trace!("reached limit, {score} *= {visibility_distance}");
+                score *= visibility_distance;
+            } else {
+                continue;
+            }
+        }
+
+        score
+    }
+}
+
+fn parse_height(input: char) -> Option<Tree> {
+    if !ASCII_DIGITS.contains(&input) {
+        return None;
+    }
+    Some(Tree {
+        // To parse an ASCII Digit char into the correct numerical value, we have
+        // to subtract the ASCII character offset ('0' as usize == 48)
+        height: input as usize - '0' as usize,
+        visible_from_outside: false, // default to false
+    })
+}
+
+// Use the itertools Either type wrapper to work with Range and Rev<Range> interchangably
+fn get_counting_order(
+    range: RangeInclusive<usize>,
+    reversed: bool,
+) -> Either<impl Iterator<Item = usize>, impl Iterator<Item = usize>> {
+    match reversed {
+        true => Either::Left(range.rev()),
+        false => Either::Right(range),
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    const VARIANTS: &'static [Variant<TreeFarm>] = &[Variant {
+        part: 2,
+        name: "two_iterators_per_loc",
+        solve: |input| solve_part2_2(input).map(Answer::from),
+    }];
+
+    type Input = TreeFarm;
+
+    fn parse(input: &str) -> Result<TreeFarm, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &TreeFarm) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &TreeFarm) -> Result<Answer, SolveError> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    fn heights(input: &str) -> Option<Vec<Vec<u32>>> {
+        let rows: Vec<Vec<u32>> = input
+            .lines()
+            .map(|line| line.chars().map(|c| c.to_digit(10)).collect())
+            .collect::<Option<_>>()?;
+        let width = rows.first()?.len();
+        match width > 0 && rows.iter().all(|row| row.len() == width) {
+            true => Some(rows),
+            false => None,
+        }
+    }
+
+    // The trees between (row, column) and the edge, nearest first
+    fn line_of_sight(rows: &[Vec<u32>], row: usize, column: usize) -> [Vec<u32>; 4] {
+        let up = (0..row).rev().map(|r| rows[r][column]).collect();
+        let down = (row + 1..rows.len()).map(|r| rows[r][column]).collect();
+        let left = rows[row][..column].iter().rev().copied().collect();
+        let right = rows[row][column + 1..].to_vec();
+        [up, down, left, right]
+    }
+
+    // Look along every line of sight of every tree, ignoring what the other trees saw
+    fn visible(input: &str) -> Option<Answer> {
+        let rows = heights(input)?;
+        let mut count: usize = 0;
+        for (row, heights) in rows.iter().enumerate() {
+            for (column, &height) in heights.iter().enumerate() {
+                let lines = line_of_sight(&rows, row, column);
+                if lines
+                    .iter()
+                    .any(|line| line.iter().all(|&tree| tree < height))
+                {
+                    count += 1;
+                }
+            }
+        }
+        Some(count.into())
+    }
+
+    fn scenic_score(input: &str) -> Option<Answer> {
+        let rows = heights(input)?;
+        let mut best = 0;
+        for (row, heights) in rows.iter().enumerate() {
+            for (column, &height) in heights.iter().enumerate() {
+                let score: usize = line_of_sight(&rows, row, column)
+                    .iter()
+                    .map(|line| match line.iter().position(|&tree| tree >= height) {
+                        Some(blocker) => blocker + 1,
+                        None => line.len(),
+                    })
+                    .product();
+                best = best.max(score);
+            }
+        }
+        Some(best.into())
+    }
+
+    #[test]
+    fn test_against_oracle() {
+        property::assert_oracle(2022, 8, 1, visible);
+        property::assert_oracle(2022, 8, 2, scenic_score);
+    }
+}
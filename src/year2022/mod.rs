@@ -0,0 +1,145 @@
+use crate::solution::Registered;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day8;
+
+pub const YEAR: u32 = 2022;
+
+// Every solved day, in order. Days are added here once they implement `Solution`
+pub static SOLUTIONS: &[&dyn Registered] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day8::Day8,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::Solver;
+    use crate::solution::SolveError;
+
+    // Inputs that parse but can't be answered, and the error each part gives instead
+    #[test]
+    fn test_solve_errors() {
+        struct TestCase {
+            description: &'static str,
+            day: u32,
+            part: u32,
+            input: &'static str,
+            expected: SolveError,
+        }
+        let invariant = |detail: &str| SolveError::invariant(detail);
+        let cases = vec![
+            TestCase {
+                description: "fewer than three elves",
+                day: 1,
+                part: 2,
+                input: "1000\n\n2000",
+                expected: invariant("expected at least 3 elves, found 2"),
+            },
+            TestCase {
+                description: "no item in both compartments",
+                day: 3,
+                part: 1,
+                input: "abcd",
+                expected: invariant("expected exactly one item in both compartments of backpack 1"),
+            },
+            TestCase {
+                description: "an incomplete group",
+                day: 3,
+                part: 2,
+                input: "aa\nbb",
+                expected: invariant("expected groups of 3 backpacks, found 2 backpacks"),
+            },
+            TestCase {
+                description: "moving more crates than a stack holds",
+                day: 5,
+                part: 1,
+                input: "[A]    \n 1   2 \n\nmove 2 from 1 to 2",
+                expected: invariant("expected a crate on stack 1 to move to stack 2"),
+            },
+            TestCase {
+                description: "moving from an empty stack",
+                day: 5,
+                part: 2,
+                input: "[A]    \n 1   2 \n\nmove 1 from 2 to 1",
+                expected: invariant("expected a crate on stack 2 to move to stack 1"),
+            },
+            TestCase {
+                description: "a stack left empty",
+                day: 5,
+                part: 1,
+                input: "[A]    \n 1   2 \n\nmove 1 from 1 to 2",
+                expected: invariant("expected a crate on stack 1 at the end"),
+            },
+            TestCase {
+                description: "no start of packet marker",
+                day: 6,
+                part: 1,
+                input: "abcabcabc",
+                expected: SolveError::NoSolution,
+            },
+            TestCase {
+                description: "an unknown register",
+                day: 10,
+                part: 1,
+                input: "addw 1",
+                expected: invariant("expected register x, y or z, found w"),
+            },
+            TestCase {
+                description: "worry levels too large to square",
+                day: 11,
+                part: 1,
+                input: concat!(
+                    "Monkey 0:\n",
+                    "  Starting items: 4294967296\n",
+                    "  Operation: new = old * old\n",
+                    "  Test: divisible by 2\n",
+                    "    If true: throw to monkey 0\n",
+                    "    If false: throw to monkey 0",
+                ),
+                expected: invariant("a worry level overflowed"),
+            },
+            TestCase {
+                description: "no way up to the end",
+                day: 12,
+                part: 1,
+                input: "SbcE",
+                expected: SolveError::NoSolution,
+            },
+        ];
+        let solvers = crate::solvers();
+        for case in cases {
+            let part = |solver: &&Solver| {
+                (solver.year, solver.day, solver.part) == (super::YEAR, case.day, case.part)
+            };
+            let parts: Vec<&Solver> = solvers.iter().filter(part).collect();
+            assert!(!parts.is_empty(), "{}: no such part", case.description);
+            for solver in parts {
+                let name = solver.name();
+                let err = match solver.run(case.input) {
+                    Ok(run) => panic!("{}: {} answered {}", case.description, name, run.answer),
+                    Err(err) => err,
+                };
+                let err = err.downcast_ref::<SolveError>();
+                assert_eq!(err, Some(&case.expected), "{}: {}", case.description, name);
+            }
+        }
+    }
+}